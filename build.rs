@@ -0,0 +1,81 @@
+//! Packs belfiore.txt into the binary form embedded by `Belfiore::init()`, so that
+//! municipalities can borrow their fields from it instead of parsing text at runtime.
+//! The records are deflate-compressed, which takes them from about 270 to 110 kB.
+//!
+//! It also exports the provenance found in belfiore.meta and the SHA-256 of belfiore.txt.
+//!
+//! Every record is: Belfiore code (4 bytes), province (2 bytes), flags (1 byte, bit 0 set
+//! for municipalities which still exist), name length (1 byte), ISTAT code length
+//! (1 byte), followed by the name and the ISTAT code, all UTF-8.
+
+use miniz_oxide::deflate::compress_to_vec;
+use sha2::{Digest, Sha256};
+use std::env;
+use std::fs;
+use std::path::Path;
+
+const MAGIC: &[u8] = b"BLF1";
+
+fn main() {
+    println!("cargo:rerun-if-changed=belfiore.txt");
+    println!("cargo:rerun-if-changed=belfiore.meta");
+    if env::var_os("CARGO_FEATURE_EMBEDDED_DATA").is_none() {
+        return;
+    }
+    let text = fs::read_to_string("belfiore.txt").expect("belfiore.txt must be UTF-8");
+    // Provenance of belfiore.txt, reported by Belfiore::version()
+    let meta = fs::read_to_string("belfiore.meta").expect("cannot read belfiore.meta");
+    for line in meta.lines() {
+        match line.split_once('=') {
+            Some(("source", v)) => println!("cargo:rustc-env=BELFIORE_SOURCE={}", v.trim()),
+            Some(("extracted", v)) => println!("cargo:rustc-env=BELFIORE_EXTRACTED={}", v.trim()),
+            _ => (),
+        }
+    }
+    println!(
+        "cargo:rustc-env=BELFIORE_SHA256={:x}",
+        Sha256::digest(text.as_bytes())
+    );
+    let mut blob = MAGIC.to_vec();
+    for (number, line) in text.lines().enumerate().filter(|(_, x)| !x.is_empty()) {
+        let fail = |reason: &str| -> ! { panic!("belfiore.txt:{}: {}", number + 1, reason) };
+        let fields: Vec<&str> = line.split(',').collect();
+        if fields.len() != 4 && fields.len() != 5 {
+            fail("expected 4 or 5 comma separated fields");
+        }
+        let (code, province, name) = (fields[0], fields[1], fields[2]);
+        let istat = fields.get(4).copied().unwrap_or("");
+        let code_ok = code.len() == 4
+            && code.as_bytes()[0].is_ascii_uppercase()
+            && code.bytes().skip(1).all(|b| b.is_ascii_digit());
+        if !code_ok {
+            fail("invalid Belfiore code");
+        }
+        if province.len() != 2 || !province.bytes().all(|b| b.is_ascii_uppercase()) {
+            fail("invalid province");
+        }
+        if code.starts_with('Z') != (province == "EE") {
+            fail("Z-codes and only Z-codes must have province EE");
+        }
+        if name.is_empty() || name.len() > usize::from(u8::MAX) {
+            fail("name must be 1 to 255 bytes");
+        }
+        if !istat.bytes().all(|b| b.is_ascii_digit()) || istat.len() > 6 {
+            fail("invalid ISTAT code");
+        }
+        let flags = match fields[3] {
+            "1" => 1,
+            "0" => 0,
+            _ => fail("active flag must be 0 or 1"),
+        };
+        blob.extend_from_slice(code.as_bytes());
+        blob.extend_from_slice(province.as_bytes());
+        blob.push(flags);
+        blob.push(name.len() as u8);
+        blob.push(istat.len() as u8);
+        blob.extend_from_slice(name.as_bytes());
+        blob.extend_from_slice(istat.as_bytes());
+    }
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("belfiore.bin");
+    fs::write(out, compress_to_vec(&blob, 10)).expect("cannot write belfiore.bin");
+}
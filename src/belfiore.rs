@@ -1,40 +1,638 @@
+#[cfg(feature = "cap")]
+use crate::cap::CapRange;
+use crate::country::{build_countries, build_successions, BirthCountry, Country, Succession};
+#[cfg(feature = "geo")]
+use crate::geo::{build_coordinates, locate, Coordinates};
+use crate::lineage::{build_lineage, Lineage};
+use crate::loader::{decode, parse_dataset, read_bytes};
+#[cfg(feature = "embedded-data")]
+use crate::loader::{parse_binary, EMBEDDED};
+use crate::search::{normalize, SearchIndex};
+#[cfg(feature = "embedded-data")]
+use crate::Error;
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::cmp::Ordering;
+#[cfg(feature = "embedded-data")]
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::Read;
+use std::ops::{Bound, RangeBounds};
+use std::path::Path;
+#[cfg(feature = "embedded-data")]
+use std::str::FromStr;
+use std::sync::Arc;
+use time::Date;
+
+/// Province code used for foreign countries
+pub const FOREIGN_PROVINCE: &str = "EE";
+
+/// Language of a municipality name
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Language {
+    Italian,
+    /// South Tyrol
+    German,
+    /// Aosta Valley
+    French,
+    /// Provinces of Trieste and Gorizia
+    Slovenian,
+    /// Val Badia and Val Gardena
+    Ladin,
+}
+
+impl Language {
+    /// Language from its ISO 639 code (ie de), as used in altnames.txt
+    pub fn from_code(code: &str) -> Option<Language> {
+        match code.to_lowercase().as_str() {
+            "it" => Some(Language::Italian),
+            "de" => Some(Language::German),
+            "fr" => Some(Language::French),
+            "sl" => Some(Language::Slovenian),
+            "lld" => Some(Language::Ladin),
+            _ => None,
+        }
+    }
+
+    /// ISO 639 code (ie de)
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::Italian => "it",
+            Language::German => "de",
+            Language::French => "fr",
+            Language::Slovenian => "sl",
+            Language::Ladin => "lld",
+        }
+    }
+}
+
+/// An official name of a municipality in a language other than Italian
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AltName {
+    pub language: Language,
+    pub name: Cow<'static, str>,
+}
+
 /// This struct represents a municipality
-#[derive(Debug, Clone, PartialEq, Default)]
+///
+/// Text fields borrow from the embedded dataset, without any copy, and own the data
+/// loaded at runtime. Build them from a `&'static str` or a `String` with `into()`.
+///
+/// Municipalities are sorted by name, then Belfiore code, as in belfiore.txt.
+/// They are displayed as `MANIAGO (PN)` and parsed, with `BELFIORE_STORE`, from a Belfiore
+/// code or a name.
+///
+/// # Examples
+///
+/// ```
+/// use codice_fiscale::belfiore::Municipality;
+///
+/// let maniago: Municipality = "E889".parse().unwrap();
+/// assert_eq!(maniago.to_string(), "MANIAGO (PN)");
+/// assert_eq!("Maniago".parse::<Municipality>().unwrap(), maniago);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
 pub struct Municipality {
-    pub name: String,
-    pub province: String,
-    pub belfiore_code: String,
+    pub name: Cow<'static, str>,
+    pub province: Cow<'static, str>,
+    pub belfiore_code: Cow<'static, str>,
+    /// Whether the municipality still exists today
+    pub active: bool,
+    /// ISTAT code: 6 digits for municipalities (ie 093025), 3 digits for foreign countries.
+    /// Only known for the entries which still exist, and only if the dataset provides it
+    pub istat_code: Option<Cow<'static, str>>,
+    /// Official names in the other languages spoken there (ie BOZEN for BOLZANO)
+    pub alt_names: Vec<AltName>,
+}
+
+impl Municipality {
+    /// True if this is a foreign country (province EE, Z-code) rather than an Italian municipality
+    pub fn is_foreign(&self) -> bool {
+        self.province == FOREIGN_PROVINCE
+    }
+
+    /// The name to display in the given language, falling back to the Italian one
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codice_fiscale::*;
+    /// use codice_fiscale::belfiore::Language;
+    ///
+    /// let bolzano = BELFIORE_STORE.get_info("Bozen").unwrap();
+    /// assert_eq!(bolzano.name_in(Language::German), "BOZEN");
+    /// assert_eq!(bolzano.name_in(Language::Italian), "BOLZANO");
+    /// assert_eq!(bolzano.name_in(Language::French), "BOLZANO");
+    /// ```
+    pub fn name_in(&self, language: Language) -> &str {
+        self.alt_names
+            .iter()
+            .find(|x| x.language == language)
+            .map_or(&self.name, |x| &x.name)
+    }
+
+    /// Iterate over every name of the municipality, the Italian one first
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(&*self.name).chain(self.alt_names.iter().map(|x| &*x.name))
+    }
+}
+
+impl PartialOrd for Municipality {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Municipality {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&self.name, &self.belfiore_code, &self.province)
+            .cmp(&(&other.name, &other.belfiore_code, &other.province))
+            .then_with(|| self.active.cmp(&other.active))
+            .then_with(|| self.istat_code.cmp(&other.istat_code))
+            .then_with(|| self.alt_names.cmp(&other.alt_names))
+    }
+}
+
+impl fmt::Display for Municipality {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({})", self.name, self.province)
+    }
 }
 
+#[cfg(feature = "embedded-data")]
+impl FromStr for Municipality {
+    type Err = Error;
+
+    /// Looks up a Belfiore code (ie E889), or else a name, in `BELFIORE_STORE`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let is_code = s.len() == 4
+            && s.starts_with(|c: char| c.is_ascii_alphabetic())
+            && s[1..].bytes().all(|b| b.is_ascii_digit());
+        if is_code {
+            return crate::BELFIORE_STORE
+                .lookup_belfiore(s)
+                .cloned()
+                .ok_or(Error::InvalidBelfioreCode);
+        }
+        crate::BELFIORE_STORE
+            .get_info(s)
+            .cloned()
+            .ok_or_else(|| Error::UnknownPlace(s.to_owned()))
+    }
+}
+
+#[cfg(feature = "embedded-data")]
+impl TryFrom<&str> for Municipality {
+    type Error = Error;
+
+    /// Same as `str::parse()`
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+/// A source of municipality data, so that the codice fiscale can be computed and parsed
+/// against your own database, a test double or a remote-backed cache instead of the
+/// embedded `Belfiore` one. Entries can be returned borrowed or owned.
+///
+/// # Examples
+///
+/// ```
+/// use codice_fiscale::*;
+/// use codice_fiscale::belfiore::{Municipality, MunicipalityStore};
+/// use std::borrow::Cow;
+///
+/// struct OnlyManiago;
+///
+/// impl MunicipalityStore for OnlyManiago {
+///     fn get_info(&self, name: &str) -> Option<Cow<'_, Municipality>> {
+///         if name.eq_ignore_ascii_case("maniago") {
+///             self.lookup_belfiore("E889")
+///         } else {
+///             None
+///         }
+///     }
+///
+///     fn lookup_belfiore(&self, belfiore: &str) -> Option<Cow<'_, Municipality>> {
+///         if belfiore == "E889" {
+///             Some(Cow::Owned(Municipality {
+///                 name: "MANIAGO".into(),
+///                 province: "PN".into(),
+///                 belfiore_code: "E889".into(),
+///                 active: true,
+///                 ..Default::default()
+///             }))
+///         } else {
+///             None
+///         }
+///     }
+/// }
+///
+/// assert!(CodiceFiscale::check_with_store("BLTMHL77S04E889G", &OnlyManiago).is_ok());
+/// assert!(CodiceFiscale::check_with_store("RSSMRA70A41H501W", &OnlyManiago).is_err());
+/// ```
+pub trait MunicipalityStore {
+    /// Obtain info for a municipality name
+    fn get_info(&self, municipality_name: &str) -> Option<Cow<'_, Municipality>>;
+    /// Obtain info for a Belfiore code
+    fn lookup_belfiore(&self, belfiore: &str) -> Option<Cow<'_, Municipality>>;
+    /// Obtain info for an ISTAT code. Stores without ISTAT codes can rely on the
+    /// default implementation, which finds nothing.
+    fn lookup_istat(&self, _istat: &str) -> Option<Cow<'_, Municipality>> {
+        None
+    }
+    /// Obtain info for a country from its ISO 3166-1 alpha-2 or alpha-3 code. The default
+    /// implementation finds nothing.
+    fn lookup_iso(&self, _iso: &str) -> Option<Cow<'_, Municipality>> {
+        None
+    }
+    /// Every municipality or country with the given name in any of its languages, ignoring
+    /// case, accents and apostrophes. The default implementation returns what `get_info()`
+    /// finds.
+    fn find_by_name(&self, name: &str) -> Vec<Cow<'_, Municipality>> {
+        self.get_info(name).into_iter().collect()
+    }
+    /// The municipalities which exist today in place of the one with the given Belfiore
+    /// code. Stores without lineage data can rely on the default implementation, which
+    /// returns the municipality itself if it still exists.
+    fn current_successors(&self, belfiore: &str) -> Vec<Cow<'_, Municipality>> {
+        self.lookup_belfiore(belfiore)
+            .filter(|m| m.active)
+            .into_iter()
+            .collect()
+    }
+    /// The country with the given Z-code, and the ones which exist today in its place.
+    /// The default implementation finds nothing.
+    fn birth_country(&self, _belfiore: &str) -> Option<BirthCountry<'_>> {
+        None
+    }
+    /// The version of the data, reported by the codici fiscali computed or parsed with the
+    /// store, which share it rather than copying it. The default implementation reports none.
+    fn version(&self) -> Option<Arc<DatasetVersion>> {
+        None
+    }
+}
+
+/// Provenance of a municipality dataset, to tell which version validated a codice fiscale
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DatasetVersion {
+    /// Where the data comes from: a description for the embedded data, the path for
+    /// files, `reader` for other readers unless set with `Belfiore::with_version()`
+    pub source: String,
+    /// When the data was extracted from the official source, if known
+    pub extracted: Option<Date>,
+    /// SHA-256 of the dataset as read, lowercase hex
+    pub sha256: String,
+}
+
+/// Error loading a municipality dataset
+#[derive(Debug, PartialEq)]
+pub enum LoadError {
+    /// The file could not be read
+    Io(String),
+    /// No municipality in the dataset
+    Empty,
+    /// The header was recognized as an official export, but required columns are missing
+    UnknownFormat,
+    /// A line (counting from 1) failed validation
+    InvalidLine { line: usize, reason: String },
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadError::Io(e) => write!(f, "Cannot read the data: {}", e),
+            LoadError::Empty => f.write_str("No entries found"),
+            LoadError::UnknownFormat => f.write_str("Unknown format: required columns are missing"),
+            LoadError::InvalidLine { line, reason } => write!(f, "Line {}: {}", line, reason),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
 /// The database, you can query it using the following functions
 pub struct Belfiore {
-    store: Vec<Municipality>,
+    pub(crate) store: Vec<Municipality>,
+    pub(crate) index: SearchIndex,
+    pub(crate) countries: Vec<Country>,
+    pub(crate) successions: Vec<Succession>,
+    pub(crate) lineage: Vec<Lineage>,
+    pub(crate) version: Arc<DatasetVersion>,
+    #[cfg(feature = "cap")]
+    pub(crate) caps: Vec<(String, CapRange)>,
+    #[cfg(feature = "geo")]
+    pub(crate) coordinates: Vec<(String, Coordinates)>,
+    /// The coordinates of the municipalities which still exist, by index in `store`
+    #[cfg(feature = "geo")]
+    pub(crate) located: Vec<(usize, Coordinates)>,
 }
 
 impl Belfiore {
-    /// Initialize the struct using belfiore.txt
+    /// Initialize the struct using belfiore.txt, as packed and compressed at build time: the
+    /// data is decompressed once, then names and codes are borrowed from it rather than
+    /// copied. Only available with the `embedded-data` feature (on by default).
+    #[cfg(feature = "embedded-data")]
     pub fn init() -> Self {
-        let db: Vec<Municipality> = include_str!("../belfiore.txt")
-            .split('\n')
-            .map(|x| x.split(',').collect::<Vec<&str>>())
-            .map(|x| Municipality {
-                name: x[2].to_owned(),
-                province: x[1].to_owned(),
-                belfiore_code: x[0].to_owned(),
-            })
-            .collect();
-        Self { store: db }
+        let version = DatasetVersion {
+            source: env!("BELFIORE_SOURCE").to_owned(),
+            extracted: Date::parse(
+                env!("BELFIORE_EXTRACTED"),
+                time::macros::format_description!("[year]-[month]-[day]"),
+            )
+            .ok(),
+            sha256: env!("BELFIORE_SHA256").to_owned(),
+        };
+        Self::from_municipalities(parse_binary(&EMBEDDED), version)
     }
-    /// Obtain info for a municipality (name, province and Belfiore code)
+
+    /// Load a dataset from a reader. Besides the belfiore.txt format (code,province,name,active
+    /// lines), the ISTAT lists of Italian municipalities and of foreign countries, the ANPR
+    /// municipality archive and the Agenzia delle Entrate table of codici catastali (as CSV)
+    /// are recognized by their header. Files can be UTF-8 or Latin-1.
+    /// Names in other languages are taken from the export when present, and from the
+    /// embedded altnames.txt otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codice_fiscale::belfiore::Belfiore;
+    ///
+    /// let data = "E889,PN,MANIAGO,1\nM999,PN,NUOVO COMUNE,1";
+    /// let store = Belfiore::from_reader(data.as_bytes()).unwrap();
+    /// assert_eq!(store.lookup_belfiore("M999").unwrap().name, "NUOVO COMUNE");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * *io* - the reader failed
+    /// * *empty* - no municipality found
+    /// * *unknown-format* - an official export without name column
+    /// * *invalid-line* - a malformed Belfiore code, province, name or active flag
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, LoadError> {
+        let bytes = read_bytes(reader)?;
+        let version = DatasetVersion {
+            source: "reader".to_owned(),
+            extracted: None,
+            sha256: format!("{:x}", Sha256::digest(&bytes)),
+        };
+        Ok(Self::from_municipalities(
+            parse_dataset(&decode(&bytes))?,
+            version,
+        ))
+    }
+
+    /// Load a dataset from a file, see `from_reader()` for the accepted formats
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, LoadError> {
+        let file = std::fs::File::open(&path).map_err(|e| LoadError::Io(e.to_string()))?;
+        let mut store = Self::from_reader(file)?;
+        Arc::make_mut(&mut store.version).source = path.as_ref().display().to_string();
+        Ok(store)
+    }
+
+    /// Record where a loaded dataset comes from and when it was extracted, keeping its hash
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codice_fiscale::belfiore::Belfiore;
+    /// use time::macros::date;
+    ///
+    /// let store = Belfiore::from_reader("E889,PN,MANIAGO,1".as_bytes())
+    ///     .unwrap()
+    ///     .with_version("ANPR archivio comuni", Some(date!(2024 - 01 - 31)));
+    /// assert_eq!(store.version().source, "ANPR archivio comuni");
+    /// assert_eq!(store.version().sha256.len(), 64);
+    /// ```
+    pub fn with_version(mut self, source: &str, extracted: Option<Date>) -> Self {
+        let version = Arc::make_mut(&mut self.version);
+        version.source = source.to_owned();
+        version.extracted = extracted;
+        self
+    }
+
+    /// Provenance and hash of the dataset
+    pub fn version(&self) -> &DatasetVersion {
+        &self.version
+    }
+
+    fn from_municipalities(mut store: Vec<Municipality>, version: DatasetVersion) -> Self {
+        add_alt_names(&mut store);
+        let index = SearchIndex::new(&store);
+        let successions = build_successions();
+        let countries = build_countries(&store, &successions);
+        #[cfg(feature = "geo")]
+        let coordinates = build_coordinates();
+        #[cfg(feature = "geo")]
+        let located = locate(&store, &coordinates);
+        Self {
+            store,
+            index,
+            countries,
+            successions,
+            lineage: build_lineage(),
+            version: Arc::new(version),
+            #[cfg(feature = "cap")]
+            caps: Vec::new(),
+            #[cfg(feature = "geo")]
+            coordinates,
+            #[cfg(feature = "geo")]
+            located,
+        }
+    }
+
+    /// Obtain info for a municipality (name, province and Belfiore code), by its Italian
+    /// name or any of its other official names
     pub fn get_info(&self, municipality_name: &str) -> Option<&Municipality> {
+        let municipality_name = municipality_name.to_uppercase();
         self.store
             .iter()
-            .find(|x| x.name == municipality_name.to_uppercase())
+            .find(|x| x.name == municipality_name)
+            .or_else(|| {
+                self.store
+                    .iter()
+                    .find(|x| x.alt_names.iter().any(|a| a.name == municipality_name))
+            })
     }
-    /// Obtain info for a Belfiore code
+    /// Obtain info for a Belfiore code. When a code was used under several names, the
+    /// entry which still exists is preferred.
     pub fn lookup_belfiore(&self, belfiore: &str) -> Option<&Municipality> {
+        let belfiore = belfiore.to_uppercase();
+        let mut found = self.store.iter().filter(|x| x.belfiore_code == belfiore);
+        let first = found.next()?;
+        if first.active {
+            return Some(first);
+        }
+        found.find(|x| x.active).or(Some(first))
+    }
+
+    /// Obtain info for an ISTAT code, either 6 digits (ie 093025) or without leading
+    /// zeros (ie 93025). Always `None` if the dataset has no ISTAT codes: the embedded one
+    /// only has a few, load the ISTAT list with `from_reader()` for all of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codice_fiscale::belfiore::Belfiore;
+    ///
+    /// let store = Belfiore::from_reader("E889,PN,MANIAGO,1,093025".as_bytes()).unwrap();
+    /// assert_eq!(store.lookup_istat("93025").unwrap().belfiore_code, "E889");
+    /// assert_eq!(store.belfiore_to_istat("E889"), Some("093025"));
+    /// ```
+    pub fn lookup_istat(&self, istat: &str) -> Option<&Municipality> {
+        let istat = istat.trim();
+        let istat =
+            if !istat.is_empty() && istat.len() < 6 && istat.bytes().all(|b| b.is_ascii_digit()) {
+                format!("{:0>6}", istat)
+            } else {
+                istat.to_owned()
+            };
+        self.store
+            .iter()
+            .find(|x| x.istat_code.as_deref() == Some(istat.as_str()))
+    }
+
+    /// Convert a Belfiore code to the ISTAT code of the same municipality
+    pub fn belfiore_to_istat(&self, belfiore: &str) -> Option<&str> {
+        self.lookup_belfiore(belfiore)?.istat_code.as_deref()
+    }
+
+    /// Convert an ISTAT code to the Belfiore code of the same municipality
+    pub fn istat_to_belfiore(&self, istat: &str) -> Option<&str> {
+        Some(&self.lookup_istat(istat)?.belfiore_code)
+    }
+
+    /// Iterate over every entry of the database, Italian municipalities and foreign countries alike
+    pub fn iter(&self) -> impl Iterator<Item = &Municipality> {
+        self.store.iter()
+    }
+
+    /// Iterate over the municipalities of a province (ie PN)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codice_fiscale::*;
+    ///
+    /// let pordenone: Vec<&str> = BELFIORE_STORE
+    ///     .by_province("PN")
+    ///     .filter(|m| m.active)
+    ///     .map(|m| m.name.as_ref())
+    ///     .collect();
+    /// assert!(pordenone.contains(&"MANIAGO"));
+    /// ```
+    pub fn by_province<'a>(&'a self, province: &'a str) -> impl Iterator<Item = &'a Municipality> {
+        self.store
+            .iter()
+            .filter(move |x| x.province.eq_ignore_ascii_case(province))
+    }
+
+    /// Iterate over the entries which still exist (`true`) or have been suppressed (`false`)
+    pub fn by_status(&self, active: bool) -> impl Iterator<Item = &Municipality> {
+        self.store.iter().filter(move |x| x.active == active)
+    }
+
+    /// Iterate over foreign countries
+    pub fn foreign(&self) -> impl Iterator<Item = &Municipality> {
+        self.store.iter().filter(|x| x.is_foreign())
+    }
+
+    /// Iterate over Italian municipalities
+    pub fn italian(&self) -> impl Iterator<Item = &Municipality> {
+        self.store.iter().filter(|x| !x.is_foreign())
+    }
+
+    /// Iterate over the entries whose Belfiore code falls in the given range. Codes are
+    /// compared as uppercase strings, so `"A".."B"` yields every code starting with A.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codice_fiscale::*;
+    ///
+    /// assert!(BELFIORE_STORE.by_code_range("Z100"..="Z199").all(|m| m.is_foreign()));
+    /// assert!(BELFIORE_STORE.by_code_range("A".."B").all(|m| m.belfiore_code.starts_with('A')));
+    /// ```
+    pub fn by_code_range<'a, R>(&'a self, range: R) -> impl Iterator<Item = &'a Municipality>
+    where
+        R: RangeBounds<&'a str>,
+    {
+        let upper = |bound: Bound<&&str>| match bound {
+            Bound::Included(s) => Bound::Included(Cow::Owned(s.to_uppercase())),
+            Bound::Excluded(s) => Bound::Excluded(Cow::Owned(s.to_uppercase())),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        let range: (Bound<Cow<str>>, Bound<Cow<str>>) =
+            (upper(range.start_bound()), upper(range.end_bound()));
         self.store
             .iter()
-            .find(|x| x.belfiore_code == belfiore.to_uppercase())
+            .filter(move |x| range.contains(&x.belfiore_code))
+    }
+}
+
+/// Adds the names from altnames.txt to the municipalities which still exist, unless the
+/// dataset already provides a name in that language
+fn add_alt_names(store: &mut [Municipality]) {
+    for line in include_str!("../altnames.txt")
+        .lines()
+        .filter(|x| !x.is_empty())
+    {
+        let fields: Vec<&str> = line.split(',').collect();
+        let language = match Language::from_code(fields[1]) {
+            Some(l) => l,
+            None => continue,
+        };
+        for m in store
+            .iter_mut()
+            .filter(|x| x.active && x.belfiore_code == fields[0])
+        {
+            if m.alt_names.iter().all(|x| x.language != language) {
+                m.alt_names.push(AltName {
+                    language,
+                    name: Cow::Borrowed(fields[2]),
+                });
+            }
+        }
+    }
+}
+
+impl MunicipalityStore for Belfiore {
+    fn get_info(&self, municipality_name: &str) -> Option<Cow<'_, Municipality>> {
+        Belfiore::get_info(self, municipality_name).map(Cow::Borrowed)
+    }
+
+    fn lookup_belfiore(&self, belfiore: &str) -> Option<Cow<'_, Municipality>> {
+        Belfiore::lookup_belfiore(self, belfiore).map(Cow::Borrowed)
+    }
+
+    fn lookup_istat(&self, istat: &str) -> Option<Cow<'_, Municipality>> {
+        Belfiore::lookup_istat(self, istat).map(Cow::Borrowed)
+    }
+
+    fn lookup_iso(&self, iso: &str) -> Option<Cow<'_, Municipality>> {
+        Belfiore::lookup_iso(self, iso).map(|country| Cow::Owned(Municipality::from(country)))
+    }
+
+    fn find_by_name(&self, name: &str) -> Vec<Cow<'_, Municipality>> {
+        self.index
+            .find(&normalize(name))
+            .map(|i| Cow::Borrowed(&self.store[i]))
+            .collect()
+    }
+
+    fn current_successors(&self, belfiore: &str) -> Vec<Cow<'_, Municipality>> {
+        Belfiore::current_successors(self, belfiore)
+            .into_iter()
+            .map(Cow::Borrowed)
+            .collect()
+    }
+
+    fn birth_country(&self, belfiore: &str) -> Option<BirthCountry<'_>> {
+        Belfiore::birth_country(self, belfiore)
+    }
+
+    fn version(&self) -> Option<Arc<DatasetVersion>> {
+        Some(Arc::clone(&self.version))
     }
 }
@@ -0,0 +1,356 @@
+//! Regenerates belfiore.txt from official files, reporting what changed.
+//!
+//! ```text
+//! belfiore-import [OPTIONS] FILE...
+//! ```
+//!
+//! Every FILE is read with `Belfiore::from_path()`: the ISTAT lists of Italian municipalities
+//! and of foreign countries, the ANPR municipality archive, the Agenzia delle Entrate table
+//! of codici catastali and files in the belfiore.txt format are recognized. Their entries are merged and compared with the current data.
+
+use codice_fiscale::belfiore::{Belfiore, Municipality};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::process;
+use time::macros::format_description;
+use time::Date;
+
+const USAGE: &str = "Usage: belfiore-import [OPTIONS] FILE...
+
+Options:
+    --current PATH      data to compare with and to update (default: belfiore.txt)
+    --output PATH       where to write the new data (default: the --current file)
+    --meta PATH         provenance file to update (default: belfiore.meta)
+    --source TEXT       source recorded in the provenance file
+    --extracted DATE    extraction date (YYYY-MM-DD) recorded in the provenance file
+    --drop-missing      remove the entries the files don't list, instead of keeping them
+                        as suppressed
+    --dry-run           only report the changes";
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Entry {
+    name: String,
+    belfiore_code: String,
+    province: String,
+    active: bool,
+    istat_code: Option<String>,
+}
+
+impl Entry {
+    fn key(&self) -> (&str, &str, &str) {
+        (&self.belfiore_code, &self.province, &self.name)
+    }
+
+    fn line(&self) -> String {
+        let mut line = format!(
+            "{},{},{},{}",
+            self.belfiore_code,
+            self.province,
+            self.name,
+            if self.active { 1 } else { 0 }
+        );
+        if let Some(istat) = &self.istat_code {
+            line.push(',');
+            line.push_str(istat);
+        }
+        line
+    }
+}
+
+/// Spells accents the way belfiore.txt does: final accented vowels get an apostrophe
+/// (CANTÙ is CANTU'), other accents are dropped (CHÂTILLON is CHATILLON)
+fn dataset_name(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut spelled = String::with_capacity(name.len());
+    for (i, &c) in chars.iter().enumerate() {
+        let base = match c {
+            'À' | 'Á' | 'Â' | 'Ä' => 'A',
+            'È' | 'É' | 'Ê' | 'Ë' => 'E',
+            'Ì' | 'Í' | 'Î' | 'Ï' => 'I',
+            'Ò' | 'Ó' | 'Ô' | 'Ö' => 'O',
+            'Ù' | 'Ú' | 'Û' | 'Ü' => 'U',
+            c => {
+                spelled.push(c);
+                continue;
+            }
+        };
+        spelled.push(base);
+        let final_vowel = chars.get(i + 1).map_or(true, |n| *n == ' ' || *n == '-');
+        if final_vowel && "ÀÈÉÌÒÙ".contains(c) {
+            spelled.push('\'');
+        }
+    }
+    spelled
+}
+
+impl From<&Municipality> for Entry {
+    fn from(m: &Municipality) -> Self {
+        Entry {
+            name: dataset_name(&m.name),
+            belfiore_code: m.belfiore_code.to_string(),
+            province: m.province.to_string(),
+            active: m.active,
+            istat_code: m.istat_code.as_ref().map(|x| x.to_string()),
+        }
+    }
+}
+
+struct Options {
+    current: String,
+    output: Option<String>,
+    meta: String,
+    source: Option<String>,
+    extracted: Option<String>,
+    drop_missing: bool,
+    dry_run: bool,
+    files: Vec<String>,
+}
+
+fn parse_args() -> Result<Options, String> {
+    let mut options = Options {
+        current: "belfiore.txt".to_string(),
+        output: None,
+        meta: "belfiore.meta".to_string(),
+        source: None,
+        extracted: None,
+        drop_missing: false,
+        dry_run: false,
+        files: Vec::new(),
+    };
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--current" => options.current = value()?,
+            "--output" => options.output = Some(value()?),
+            "--meta" => options.meta = value()?,
+            "--source" => options.source = Some(value()?),
+            "--extracted" => options.extracted = Some(value()?),
+            "--drop-missing" => options.drop_missing = true,
+            "--dry-run" => options.dry_run = true,
+            "-h" | "--help" => return Err(String::new()),
+            a if a.starts_with("--") => return Err(format!("unknown option {}", a)),
+            _ => options.files.push(arg),
+        }
+    }
+    if options.files.is_empty() {
+        return Err("no input file".to_string());
+    }
+    if let Some(date) = &options.extracted {
+        if Date::parse(date, format_description!("[year]-[month]-[day]")).is_err() {
+            return Err(format!("invalid extraction date {}", date));
+        }
+    }
+    Ok(options)
+}
+
+fn load(path: &str) -> Result<Vec<Entry>, String> {
+    let store = Belfiore::from_path(path).map_err(|e| format!("{}: {}", path, e))?;
+    Ok(store.iter().map(Entry::from).collect())
+}
+
+/// Merges the entries of every file: an entry listed as existing by any file exists, and
+/// ISTAT codes are taken from whichever file has them
+fn merge(files: Vec<Vec<Entry>>) -> Vec<Entry> {
+    let mut merged: BTreeMap<(String, String, String), Entry> = BTreeMap::new();
+    for entry in files.into_iter().flatten() {
+        let key = (
+            entry.belfiore_code.clone(),
+            entry.province.clone(),
+            entry.name.clone(),
+        );
+        match merged.get_mut(&key) {
+            Some(found) => {
+                found.active |= entry.active;
+                if found.istat_code.is_none() {
+                    found.istat_code = entry.istat_code;
+                }
+            }
+            None => {
+                merged.insert(key, entry);
+            }
+        }
+    }
+    merged.into_values().collect()
+}
+
+/// Problems which would make the new data unusable
+fn validate(entries: &[Entry]) -> Vec<String> {
+    let mut errors = Vec::new();
+    let mut active: BTreeMap<&str, Vec<&Entry>> = BTreeMap::new();
+    for e in entries.iter().filter(|e| e.active) {
+        active.entry(&e.belfiore_code).or_default().push(e);
+    }
+    for (code, found) in active.iter().filter(|(_, found)| found.len() > 1) {
+        let names: Vec<&str> = found.iter().map(|e| e.name.as_str()).collect();
+        errors.push(format!(
+            "{} is used by {} existing entries: {}",
+            code,
+            found.len(),
+            names.join(", ")
+        ));
+    }
+    let mut istat: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for e in entries.iter().filter(|e| e.active) {
+        if let Some(code) = &e.istat_code {
+            istat.entry(code).or_default().push(&e.belfiore_code);
+        }
+    }
+    for (code, found) in istat.iter().filter(|(_, found)| found.len() > 1) {
+        errors.push(format!(
+            "ISTAT code {} is used by {}",
+            code,
+            found.join(", ")
+        ));
+    }
+    if !entries.iter().any(|e| e.belfiore_code.starts_with('Z')) {
+        errors.push("no foreign country: add the ISTAT list of foreign countries".to_string());
+    }
+    errors
+}
+
+#[derive(Default)]
+struct Report {
+    added: Vec<Entry>,
+    removed: Vec<Entry>,
+    renamed: Vec<(Entry, Entry)>,
+    suppressed: Vec<Entry>,
+    restored: Vec<Entry>,
+    /// Suppressed entries the files don't list, usually because they only list the
+    /// municipalities which exist today
+    historical: Vec<Entry>,
+}
+
+fn compare(current: &[Entry], new: &[Entry]) -> Report {
+    let current_keys: BTreeSet<_> = current.iter().map(Entry::key).collect();
+    let new_keys: BTreeSet<_> = new.iter().map(Entry::key).collect();
+    let mut report = Report::default();
+    let (mut removed, historical): (Vec<&Entry>, Vec<&Entry>) = current
+        .iter()
+        .filter(|e| !new_keys.contains(&e.key()))
+        .partition(|e| e.active);
+    report.historical = historical.into_iter().cloned().collect();
+    for e in new.iter().filter(|e| !current_keys.contains(&e.key())) {
+        // Same code and province under another name, which is gone: a rename
+        let old = removed
+            .iter()
+            .position(|o| o.belfiore_code == e.belfiore_code && o.province == e.province);
+        match old {
+            Some(i) => report.renamed.push((removed.remove(i).clone(), e.clone())),
+            None => report.added.push(e.clone()),
+        }
+    }
+    report.removed = removed.into_iter().cloned().collect();
+    for e in new {
+        if let Some(old) = current.iter().find(|o| o.key() == e.key()) {
+            if old.active && !e.active {
+                report.suppressed.push(e.clone());
+            } else if !old.active && e.active {
+                report.restored.push(e.clone());
+            }
+        }
+    }
+    report
+}
+
+fn print_report(report: &Report, drop_missing: bool) {
+    println!("Added: {}", report.added.len());
+    for e in &report.added {
+        println!("  + {}", e.line());
+    }
+    println!("Renamed: {}", report.renamed.len());
+    for (old, new) in &report.renamed {
+        println!("  ~ {} {} -> {}", new.belfiore_code, old.name, new.name);
+    }
+    println!(
+        "Existing entries not listed ({}): {}",
+        if drop_missing {
+            "removed"
+        } else {
+            "kept as suppressed"
+        },
+        report.removed.len()
+    );
+    for e in &report.removed {
+        println!("  - {}", e.line());
+    }
+    println!("Suppressed: {}", report.suppressed.len());
+    for e in &report.suppressed {
+        println!("  x {}", e.line());
+    }
+    println!("Restored: {}", report.restored.len());
+    for e in &report.restored {
+        println!("  ! {}", e.line());
+    }
+    println!(
+        "Suppressed entries not listed ({}): {}",
+        if drop_missing { "removed" } else { "kept" },
+        report.historical.len()
+    );
+}
+
+fn run(options: Options) -> Result<(), String> {
+    let current = load(&options.current)?;
+    let files = options
+        .files
+        .iter()
+        .map(|f| load(f))
+        .collect::<Result<Vec<Vec<Entry>>, String>>()?;
+    let mut new = merge(files);
+
+    let report = compare(&current, &new);
+    if !options.drop_missing {
+        // Old names are kept as suppressed entries, as belfiore.txt does for renames
+        let missing = report
+            .removed
+            .iter()
+            .chain(report.renamed.iter().map(|(old, _)| old))
+            .chain(report.historical.iter());
+        new.extend(missing.map(|e| Entry {
+            active: false,
+            ..e.clone()
+        }));
+    }
+    // Same order as belfiore.txt: by name, then code
+    new.sort();
+    let errors = validate(&new);
+    print_report(&report, options.drop_missing);
+    if !errors.is_empty() {
+        return Err(format!("validation failed:\n  {}", errors.join("\n  ")));
+    }
+    if options.dry_run {
+        return Ok(());
+    }
+
+    let lines: Vec<String> = new.iter().map(Entry::line).collect();
+    let output = options.output.as_ref().unwrap_or(&options.current);
+    fs::write(output, lines.join("\n")).map_err(|e| format!("{}: {}", output, e))?;
+    let meta = format!(
+        "source={}\nextracted={}\n",
+        options
+            .source
+            .clone()
+            .unwrap_or_else(|| options.files.join(" ")),
+        options.extracted.as_deref().unwrap_or("")
+    );
+    fs::write(&options.meta, meta).map_err(|e| format!("{}: {}", options.meta, e))?;
+    println!("Wrote {} entries to {}", lines.len(), output);
+    Ok(())
+}
+
+fn main() {
+    let options = match parse_args() {
+        Ok(o) => o,
+        Err(e) => {
+            if !e.is_empty() {
+                eprintln!("{}\n", e);
+            }
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+    if let Err(e) = run(options) {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,206 @@
+use crate::Error;
+use std::convert::TryFrom;
+use time::{Date, Month, OffsetDateTime};
+
+const MONTH_NAMES: [&str; 12] = [
+    "GENNAIO",
+    "FEBBRAIO",
+    "MARZO",
+    "APRILE",
+    "MAGGIO",
+    "GIUGNO",
+    "LUGLIO",
+    "AGOSTO",
+    "SETTEMBRE",
+    "OTTOBRE",
+    "NOVEMBRE",
+    "DICEMBRE",
+];
+
+/// A way of writing a date
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum DateFormat {
+    /// 1977-11-04
+    Iso,
+    /// Day, month and year separated by `/`, `-`, `.` or spaces: 04/11/1977, 4-11-77
+    Dmy,
+    /// Day, Italian month name or its first three letters, year: 4 novembre 1977, 4 nov 77,
+    /// 1° gennaio 2000
+    ItalianMonth,
+}
+
+/// Parses birthdates written in the accepted formats. A two-digit year is read as the only
+/// year of 1900 or later, and not in the future, which ends with those digits; when there
+/// are two, the date is reported as ambiguous.
+///
+/// # Examples
+///
+/// ```
+/// use codice_fiscale::birthdate::*;
+/// use codice_fiscale::Error;
+/// use time::macros::date;
+///
+/// let parser = BirthdateParser::default().today(date!(2024 - 01 - 01));
+/// assert_eq!(parser.parse("4 novembre 1977"), Ok(date!(1977 - 11 - 04)));
+/// assert_eq!(parser.parse("4-11-77"), Ok(date!(1977 - 11 - 04)));
+/// assert_eq!(
+///     parser.parse("4/11/10"),
+///     Err(Error::AmbiguousBirthdate(vec![
+///         "1910-11-04".to_string(),
+///         "2010-11-04".to_string()
+///     ]))
+/// );
+///
+/// let strict = BirthdateParser::new(&[DateFormat::Iso]);
+/// assert!(strict.parse("04/11/1977").is_err());
+/// assert!(BirthdateParser::default().full_years().parse("4-11-77").is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct BirthdateParser {
+    formats: Vec<DateFormat>,
+    today: Option<Date>,
+    full_years: bool,
+}
+
+impl Default for BirthdateParser {
+    /// Accepts every format
+    fn default() -> Self {
+        BirthdateParser::new(&[DateFormat::Iso, DateFormat::Dmy, DateFormat::ItalianMonth])
+    }
+}
+
+impl BirthdateParser {
+    pub fn new(formats: &[DateFormat]) -> Self {
+        BirthdateParser {
+            formats: formats.to_vec(),
+            today: None,
+            full_years: false,
+        }
+    }
+
+    /// Only accept four-digit years, so that the result doesn't depend on today's date
+    pub fn full_years(mut self) -> Self {
+        self.full_years = true;
+        self
+    }
+
+    /// The date two-digit years are resolved against, today by default
+    pub fn today(mut self, today: Date) -> Self {
+        self.today = Some(today);
+        self
+    }
+
+    /// # Errors
+    ///
+    /// * *invalid-birthdate* - not a valid date in one of the accepted formats
+    /// * *ambiguous-birthdate* - a two-digit year which could be of either century: the
+    ///   candidates are given as YYYY-MM-DD
+    pub fn parse(&self, text: &str) -> Result<Date, Error> {
+        let invalid = || Error::InvalidBirthdate(Some(text.to_owned()));
+        let fields = split(text).ok_or_else(invalid)?;
+        for format in &self.formats {
+            let parsed = match format {
+                DateFormat::Iso => iso(text.trim(), &fields),
+                DateFormat::Dmy => numeric_month(&fields),
+                DateFormat::ItalianMonth => month_name(&fields),
+            };
+            if let Some((day, month, year)) = parsed {
+                return self.resolve(day, month, year).ok_or_else(invalid)?;
+            }
+        }
+        Err(invalid())
+    }
+
+    fn resolve(&self, day: u8, month: Month, year: &str) -> Option<Result<Date, Error>> {
+        let number: i32 = year.parse().ok()?;
+        if year.len() == 4 {
+            return Date::from_calendar_date(number, month, day).ok().map(Ok);
+        }
+        if self.full_years {
+            return None;
+        }
+        let today = self
+            .today
+            .unwrap_or_else(|| OffsetDateTime::now_utc().date());
+        let candidates: Vec<Date> = [1900, 2000, 2100]
+            .iter()
+            .filter_map(|century| Date::from_calendar_date(century + number, month, day).ok())
+            .filter(|date| *date <= today)
+            .collect();
+        match candidates.len() {
+            0 => None,
+            1 => Some(Ok(candidates[0])),
+            _ => Some(Err(Error::AmbiguousBirthdate(
+                candidates.iter().map(Date::to_string).collect(),
+            ))),
+        }
+    }
+}
+
+/// The fields of a date, None if it has other characters than letters, digits and separators
+fn split(text: &str) -> Option<Vec<String>> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    for c in text.trim().chars().flat_map(char::to_uppercase) {
+        match c {
+            '/' | '-' | '.' | ' ' | ',' => {
+                if !field.is_empty() {
+                    fields.push(std::mem::take(&mut field));
+                }
+            }
+            // 1° gennaio
+            '°' | 'º' => (),
+            c if c.is_alphanumeric() => field.push(c),
+            _ => return None,
+        }
+    }
+    if !field.is_empty() {
+        fields.push(field);
+    }
+    Some(fields)
+}
+
+fn day(field: &str) -> Option<u8> {
+    if field.is_empty() || field.len() > 2 || !field.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    field.parse().ok()
+}
+
+fn year(field: &str) -> Option<&str> {
+    if (field.len() == 2 || field.len() == 4) && field.bytes().all(|b| b.is_ascii_digit()) {
+        Some(field)
+    } else {
+        None
+    }
+}
+
+fn iso<'a>(text: &str, fields: &'a [String]) -> Option<(u8, Month, &'a str)> {
+    if fields.len() != 3 || fields[0].len() != 4 || text.matches('-').count() != 2 {
+        return None;
+    }
+    let month = Month::try_from(day(&fields[1])?).ok()?;
+    Some((day(&fields[2])?, month, year(&fields[0])?))
+}
+
+fn numeric_month(fields: &[String]) -> Option<(u8, Month, &str)> {
+    if fields.len() != 3 {
+        return None;
+    }
+    let month = Month::try_from(day(&fields[1])?).ok()?;
+    Some((day(&fields[0])?, month, year(&fields[2])?))
+}
+
+fn month_name(fields: &[String]) -> Option<(u8, Month, &str)> {
+    if fields.len() != 3 || fields[1].len() < 3 {
+        return None;
+    }
+    let index = MONTH_NAMES
+        .iter()
+        .position(|name| name.starts_with(&*fields[1]))?;
+    if fields[1].len() != 3 && fields[1] != MONTH_NAMES[index] {
+        return None;
+    }
+    let month = Month::try_from(index as u8 + 1).ok()?;
+    Some((day(&fields[0])?, month, year(&fields[2])?))
+}
@@ -0,0 +1,238 @@
+use crate::belfiore::{Municipality, MunicipalityStore};
+use crate::birthdate::BirthdateParser;
+use crate::names::letters;
+use crate::transliteration::{is_latin, Transliteration};
+use crate::{CodiceFiscale, Error, Gender, PersonData};
+use std::borrow::Cow;
+use time::{Date, OffsetDateTime};
+
+#[derive(Debug, Clone)]
+enum Birthdate {
+    Date(Date),
+    Text(String),
+}
+
+#[derive(Debug, Clone)]
+enum Birthplace {
+    Municipality {
+        name: String,
+        province: Option<String>,
+    },
+    Belfiore(String),
+    Country(String),
+}
+
+/// Builds a `PersonData`, validating every field, or directly a `CodiceFiscale`
+///
+/// # Examples
+///
+/// ```
+/// use codice_fiscale::*;
+///
+/// let cf = PersonData::builder()
+///     .name("Michele")
+///     .surname("Beltrame")
+///     .birthdate_str("04/11/1977")
+///     .gender(Gender::M)
+///     .born_in("Maniago", Some("PN"))
+///     .codice_fiscale()
+///     .unwrap();
+/// assert_eq!(cf.get_codice(), "BLTMHL77S04E889G");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PersonDataBuilder {
+    name: Option<String>,
+    surname: Option<String>,
+    birthdate: Option<Birthdate>,
+    gender: Option<Gender>,
+    birthplace: Option<Birthplace>,
+    transliteration: Option<Transliteration>,
+}
+
+impl PersonDataBuilder {
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_owned());
+        self
+    }
+
+    pub fn surname(mut self, surname: &str) -> Self {
+        self.surname = Some(surname.to_owned());
+        self
+    }
+
+    pub fn birthdate(mut self, birthdate: Date) -> Self {
+        self.birthdate = Some(Birthdate::Date(birthdate));
+        self
+    }
+
+    /// The birthdate as text, in any format accepted by `birthdate::BirthdateParser`: use
+    /// `birthdate()` with your own parser to restrict them
+    pub fn birthdate_str(mut self, birthdate: &str) -> Self {
+        self.birthdate = Some(Birthdate::Text(birthdate.to_owned()));
+        self
+    }
+
+    pub fn gender(mut self, gender: Gender) -> Self {
+        self.gender = Some(gender);
+        self
+    }
+
+    /// Born in an Italian municipality, by any of its official names. The province (ie PN)
+    /// is needed when municipalities of different provinces have the same name.
+    pub fn born_in(mut self, municipality: &str, province: Option<&str>) -> Self {
+        self.birthplace = Some(Birthplace::Municipality {
+            name: municipality.to_owned(),
+            province: province.map(str::to_uppercase),
+        });
+        self
+    }
+
+    /// Born in the municipality or country with the given Belfiore code (ie E889, Z112)
+    pub fn born_in_belfiore(mut self, belfiore: &str) -> Self {
+        self.birthplace = Some(Birthplace::Belfiore(belfiore.to_owned()));
+        self
+    }
+
+    /// Born abroad, in the country with the given name (ie Germania), ISO 3166-1 code (DE or
+    /// DEU) or Z-code
+    pub fn born_abroad(mut self, country: &str) -> Self {
+        self.birthplace = Some(Birthplace::Country(country.to_owned()));
+        self
+    }
+
+    /// How names in other scripts are turned into Latin letters; by default, names must be
+    /// in the Latin, Cyrillic or Greek script
+    pub fn transliteration(mut self, transliteration: Transliteration) -> Self {
+        self.transliteration = Some(transliteration);
+        self
+    }
+
+    /// Validates the data, looking places up in `BELFIORE_STORE`
+    ///
+    /// # Errors
+    ///
+    /// * *missing-field* - a field was not set
+    /// * *invalid-name*, *invalid-surname* - no letters, or letters which can't be
+    ///   transliterated
+    /// * *invalid-birthdate* - not a date in a known format, before 1900 or in the future
+    /// * *ambiguous-birthdate* - a two-digit year which could be of either century
+    /// * *invalid-belfiore-code* - no place with that Belfiore code
+    /// * *unknown-place* - no municipality or country with that name
+    /// * *ambiguous-place* - municipalities of several provinces have that name
+    #[cfg(feature = "embedded-data")]
+    pub fn build(self) -> Result<PersonData, Error> {
+        self.build_with_store(&*crate::BELFIORE_STORE)
+    }
+
+    /// Same as `build()`, looking places up in the given database. Places are found by name
+    /// through `MunicipalityStore::find_by_name()` and by ISO code through
+    /// `MunicipalityStore::lookup_iso()`.
+    pub fn build_with_store<S: MunicipalityStore + ?Sized>(
+        self,
+        store: &S,
+    ) -> Result<PersonData, Error> {
+        let person = PersonData {
+            name: self.name.ok_or(Error::MissingField("name"))?,
+            surname: self.surname.ok_or(Error::MissingField("surname"))?,
+            birthdate: validate_birthdate(self.birthdate.ok_or(Error::MissingField("birthdate"))?)?,
+            gender: self.gender.ok_or(Error::MissingField("gender"))?,
+            place_of_birth: find_birthplace(
+                store,
+                self.birthplace.ok_or(Error::MissingField("birthplace"))?,
+            )?,
+        };
+        let person = match self.transliteration {
+            Some(transliteration) => transliteration.apply(&person)?,
+            None => person,
+        };
+        if letters(&person.surname).is_empty() || !is_latin(&person.surname) {
+            return Err(Error::InvalidSurname);
+        }
+        if letters(&person.name).is_empty() || !is_latin(&person.name) {
+            return Err(Error::InvalidName);
+        }
+        Ok(person)
+    }
+
+    /// Validates the data and computes the codice fiscale, looking places up in
+    /// `BELFIORE_STORE`
+    ///
+    /// # Errors
+    ///
+    /// The same as `build()`
+    #[cfg(feature = "embedded-data")]
+    pub fn codice_fiscale(self) -> Result<CodiceFiscale, Error> {
+        self.codice_fiscale_with_store(&*crate::BELFIORE_STORE)
+    }
+
+    /// Same as `codice_fiscale()`, looking places up in the given database
+    pub fn codice_fiscale_with_store<S: MunicipalityStore + ?Sized>(
+        self,
+        store: &S,
+    ) -> Result<CodiceFiscale, Error> {
+        CodiceFiscale::new_with_store(&self.build_with_store(store)?, store)
+    }
+}
+
+fn validate_birthdate(birthdate: Birthdate) -> Result<String, Error> {
+    let (date, text) = match birthdate {
+        Birthdate::Date(date) => (date, date.to_string()),
+        Birthdate::Text(text) => (BirthdateParser::default().parse(&text)?, text),
+    };
+    if date.year() >= 1900 && date <= OffsetDateTime::now_utc().date() {
+        Ok(date.to_string())
+    } else {
+        Err(Error::InvalidBirthdate(Some(text)))
+    }
+}
+
+fn find_birthplace<S: MunicipalityStore + ?Sized>(
+    store: &S,
+    birthplace: Birthplace,
+) -> Result<Municipality, Error> {
+    let found = match birthplace {
+        Birthplace::Belfiore(code) => store
+            .lookup_belfiore(&code)
+            .ok_or(Error::InvalidBelfioreCode)?,
+        Birthplace::Country(country) => {
+            let code = match store.lookup_iso(&country) {
+                Some(c) => c.belfiore_code.to_string(),
+                None => store
+                    .lookup_belfiore(&country)
+                    .into_iter()
+                    .chain(store.find_by_name(&country))
+                    .find(|m| m.is_foreign())
+                    .ok_or(Error::UnknownPlace(country))?
+                    .belfiore_code
+                    .to_string(),
+            };
+            store
+                .lookup_belfiore(&code)
+                .ok_or(Error::InvalidBelfioreCode)?
+        }
+        Birthplace::Municipality { name, province } => {
+            let mut found: Vec<Cow<Municipality>> = store
+                .find_by_name(&name)
+                .into_iter()
+                .filter(|m| !m.is_foreign())
+                .filter(|m| province.as_ref().map_or(true, |p| *m.province == **p))
+                .collect();
+            // A suppressed municipality only if no existing one has the name
+            if found.iter().any(|m| m.active) {
+                found.retain(|m| m.active);
+            }
+            found.sort_by(|a, b| a.belfiore_code.cmp(&b.belfiore_code));
+            found.dedup_by(|a, b| a.belfiore_code == b.belfiore_code);
+            if found.len() > 1 {
+                return Err(Error::AmbiguousPlace(
+                    found
+                        .iter()
+                        .map(|m| format!("{} ({})", m.name, m.province))
+                        .collect(),
+                ));
+            }
+            found.pop().ok_or(Error::UnknownPlace(name))?
+        }
+    };
+    Ok(found.into_owned())
+}
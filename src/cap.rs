@@ -0,0 +1,144 @@
+use crate::belfiore::{Belfiore, LoadError, Municipality};
+use crate::loader::{invalid, read_text};
+use std::io::Read;
+
+/// A range of postal codes (CAP) of a municipality, both ends included. Most municipalities
+/// have a single CAP, so that `first == last`; big cities have one per district.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CapRange {
+    pub first: String,
+    pub last: String,
+}
+
+impl CapRange {
+    /// True if the five-digit `cap` falls in the range
+    pub fn contains(&self, cap: &str) -> bool {
+        is_cap(cap) && self.first.as_str() <= cap && cap <= self.last.as_str()
+    }
+}
+
+fn is_cap(cap: &str) -> bool {
+    cap.len() == 5 && cap.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Parses `code,first,last` (or `code,cap`) lines, sorting them by Belfiore code
+pub(crate) fn parse_caps(text: &str) -> Result<Vec<(String, CapRange)>, LoadError> {
+    let mut caps = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        let (first, last) = match fields.len() {
+            2 => (fields[1], fields[1]),
+            3 => (fields[1], fields[2]),
+            _ => {
+                return Err(invalid(
+                    number + 1,
+                    "expected 2 or 3 comma separated fields",
+                ))
+            }
+        };
+        if !is_cap(first) || !is_cap(last) || first > last {
+            return Err(invalid(number + 1, "invalid CAP range"));
+        }
+        caps.push((
+            fields[0].to_uppercase(),
+            CapRange {
+                first: first.to_owned(),
+                last: last.to_owned(),
+            },
+        ));
+    }
+    if caps.is_empty() {
+        return Err(LoadError::Empty);
+    }
+    caps.sort();
+    Ok(caps)
+}
+
+impl Belfiore {
+    /// Load a CAP table: no complete and freely licensed one is available to embed, so the
+    /// table is empty until this is called. Lines are `code,first,last` (ie
+    /// `H501,00118,00199`) or `code,cap`, and replace any table loaded before.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codice_fiscale::belfiore::Belfiore;
+    ///
+    /// let store = Belfiore::init()
+    ///     .with_caps("E889,33085\nG886,33080".as_bytes())
+    ///     .unwrap();
+    /// assert_eq!(store.lookup_cap("33080")[0].name, "PORCIA");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * *io* - the reader failed
+    /// * *empty* - no CAP found
+    /// * *invalid-line* - a malformed line or CAP
+    pub fn with_caps<R: Read>(mut self, reader: R) -> Result<Self, LoadError> {
+        self.caps = parse_caps(&read_text(reader)?)?;
+        Ok(self)
+    }
+
+    /// The CAP ranges of a municipality, empty if it isn't in the CAP table
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codice_fiscale::belfiore::Belfiore;
+    ///
+    /// let store = Belfiore::init()
+    ///     .with_caps("H501,00118,00199".as_bytes())
+    ///     .unwrap();
+    /// let rome = store.caps("H501");
+    /// assert_eq!(rome[0].first, "00118");
+    /// assert_eq!(rome[0].last, "00199");
+    /// ```
+    pub fn caps(&self, belfiore: &str) -> Vec<&CapRange> {
+        let belfiore = belfiore.to_uppercase();
+        let start = self.caps.partition_point(|(code, _)| *code < belfiore);
+        self.caps[start..]
+            .iter()
+            .take_while(|(code, _)| *code == belfiore)
+            .map(|(_, range)| range)
+            .collect()
+    }
+
+    /// The municipalities which still exist and use the given CAP
+    pub fn lookup_cap(&self, cap: &str) -> Vec<&Municipality> {
+        let mut found: Vec<&Municipality> = Vec::new();
+        for (code, _) in self.caps.iter().filter(|(_, range)| range.contains(cap)) {
+            if let Some(m) = self.lookup_belfiore(code).filter(|m| m.active) {
+                if !found.iter().any(|x| x.belfiore_code == m.belfiore_code) {
+                    found.push(m);
+                }
+            }
+        }
+        found
+    }
+
+    /// Whether a CAP belongs to a municipality: `None` if the municipality isn't in the CAP
+    /// table, so that the caller can tell an unknown from a wrong CAP
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codice_fiscale::belfiore::Belfiore;
+    ///
+    /// let store = Belfiore::init().with_caps("E889,33085".as_bytes()).unwrap();
+    /// assert_eq!(store.is_cap_of("E889", "33085"), Some(true));
+    /// assert_eq!(store.is_cap_of("E889", "33170"), Some(false));
+    /// assert_eq!(store.is_cap_of("G888", "33170"), None);
+    /// ```
+    pub fn is_cap_of(&self, belfiore: &str, cap: &str) -> Option<bool> {
+        let ranges = self.caps(belfiore);
+        if ranges.is_empty() {
+            return None;
+        }
+        Some(ranges.iter().any(|range| range.contains(cap)))
+    }
+}
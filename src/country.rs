@@ -0,0 +1,353 @@
+use crate::belfiore::{Belfiore, Municipality, FOREIGN_PROVINCE};
+use std::collections::HashMap;
+use time::Date;
+
+const SUCCESSION_DATE_FORMAT: &[time::format_description::FormatItem<'static>] =
+    time::macros::format_description!("[year]-[month]-[day]");
+
+/// Continent of a foreign country, as implied by the hundreds of its Z-code
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Continent {
+    /// Z1xx
+    Europe,
+    /// Z2xx
+    Asia,
+    /// Z3xx
+    Africa,
+    /// Z4xx
+    NorthAmerica,
+    /// Z5xx, including the Caribbean
+    CentralAmerica,
+    /// Z6xx
+    SouthAmerica,
+    /// Z7xx
+    Oceania,
+    /// Z8xx, arctic dependencies
+    Arctic,
+    /// Z9xx, antarctic dependencies
+    Antarctica,
+}
+
+impl Continent {
+    /// Continent implied by a Z-code, `None` for Italian municipalities or malformed codes
+    pub fn from_belfiore(belfiore: &str) -> Option<Continent> {
+        let code = belfiore.to_uppercase();
+        if code.len() != 4
+            || !code.starts_with('Z')
+            || !code[1..].bytes().all(|b| b.is_ascii_digit())
+        {
+            return None;
+        }
+        // South Sudan was given a free code in the Z9 block when it became independent
+        if code == "Z907" {
+            return Some(Continent::Africa);
+        }
+        match &code[1..2] {
+            "1" => Some(Continent::Europe),
+            "2" => Some(Continent::Asia),
+            "3" => Some(Continent::Africa),
+            "4" => Some(Continent::NorthAmerica),
+            "5" => Some(Continent::CentralAmerica),
+            "6" => Some(Continent::SouthAmerica),
+            "7" => Some(Continent::Oceania),
+            "8" => Some(Continent::Arctic),
+            "9" => Some(Continent::Antarctica),
+            _ => None,
+        }
+    }
+}
+
+/// This struct represents a foreign country (province EE, Z-code)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Country {
+    pub name: String,
+    pub belfiore_code: String,
+    pub continent: Continent,
+    /// ISO 3166-1 alpha-2 code, missing for states and territories which no longer exist
+    pub iso_alpha2: Option<String>,
+    /// ISO 3166-1 alpha-3 code, missing for states and territories which no longer exist
+    pub iso_alpha3: Option<String>,
+    /// Date the state came into existence, if it was formed from other states in the database
+    pub valid_from: Option<Date>,
+    /// Date the state ceased to exist, if it was succeeded by other states in the database
+    pub valid_to: Option<Date>,
+    /// Z-codes of the states this one was formed from or absorbed
+    pub predecessors: Vec<String>,
+    /// Z-codes of the states which took over (part of) this one
+    pub successors: Vec<String>,
+}
+
+/// How a state took over the territory of another one
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SuccessionKind {
+    /// A new state broke away, the predecessor kept existing (ie Croatia from Yugoslavia)
+    Split,
+    /// The predecessor ceased to exist in favour of new states (ie the USSR), including mergers
+    Dissolution,
+    /// The predecessor was absorbed by an already existing state (ie the GDR by Germany)
+    Annexation,
+}
+
+/// A change of sovereignty between two foreign countries, from succession.txt
+#[derive(Debug, Clone, PartialEq)]
+pub struct Succession {
+    pub predecessor: String,
+    pub successor: String,
+    pub date: Date,
+    pub kind: SuccessionKind,
+}
+
+/// Country of birth of someone born abroad
+#[derive(Debug, Clone, PartialEq)]
+pub struct BirthCountry<'a> {
+    /// The state encoded in the codice fiscale, which existed at the birth date
+    pub historical: &'a Country,
+    /// The states which exist today in its place (the same one if nothing changed)
+    pub present_day: Vec<&'a Country>,
+}
+
+impl From<&Country> for Municipality {
+    /// The database entry to use as place of birth in `PersonData`
+    fn from(country: &Country) -> Self {
+        Municipality {
+            name: country.name.clone().into(),
+            province: FOREIGN_PROVINCE.into(),
+            belfiore_code: country.belfiore_code.clone().into(),
+            active: false,
+            istat_code: None,
+            alt_names: Vec::new(),
+        }
+    }
+}
+
+/// A place of birth: either an Italian municipality or a foreign country
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Place<'a> {
+    Municipality(&'a Municipality),
+    Country(&'a Country),
+}
+
+impl<'a> Place<'a> {
+    pub fn name(&self) -> &'a str {
+        match self {
+            Place::Municipality(m) => &m.name,
+            Place::Country(c) => &c.name,
+        }
+    }
+
+    pub fn belfiore_code(&self) -> &'a str {
+        match self {
+            Place::Municipality(m) => &m.belfiore_code,
+            Place::Country(c) => &c.belfiore_code,
+        }
+    }
+}
+
+pub(crate) fn build_successions() -> Vec<Succession> {
+    include_str!("../succession.txt")
+        .lines()
+        .filter(|x| !x.is_empty())
+        .map(|x| x.split(',').collect::<Vec<&str>>())
+        .map(|x| Succession {
+            predecessor: x[0].to_owned(),
+            successor: x[1].to_owned(),
+            date: Date::parse(x[2], SUCCESSION_DATE_FORMAT).unwrap(),
+            kind: match x[3] {
+                "split" => SuccessionKind::Split,
+                "dissolution" => SuccessionKind::Dissolution,
+                _ => SuccessionKind::Annexation,
+            },
+        })
+        .collect()
+}
+
+/// Builds one country per Z-code found in the store, named and coded after
+/// countries.txt when the country still exists
+pub(crate) fn build_countries(store: &[Municipality], successions: &[Succession]) -> Vec<Country> {
+    let iso: HashMap<&str, Vec<&str>> = include_str!("../countries.txt")
+        .lines()
+        .filter(|x| !x.is_empty())
+        .map(|x| x.split(',').collect::<Vec<&str>>())
+        .map(|x| (x[0], x))
+        .collect();
+
+    let mut countries: Vec<Country> = Vec::new();
+    for m in store.iter().filter(|x| x.is_foreign()) {
+        if countries.iter().any(|x| x.belfiore_code == m.belfiore_code) {
+            continue;
+        }
+        let continent = match Continent::from_belfiore(&m.belfiore_code) {
+            Some(c) => c,
+            None => continue,
+        };
+        let current = iso.get(&*m.belfiore_code);
+        countries.push(Country {
+            name: current.map_or_else(|| m.name.to_string(), |x| x[3].to_string()),
+            belfiore_code: m.belfiore_code.to_string(),
+            continent,
+            iso_alpha2: current.map(|x| x[1].to_string()),
+            iso_alpha3: current.map(|x| x[2].to_string()),
+            valid_from: successions
+                .iter()
+                .filter(|x| x.successor == m.belfiore_code && x.kind != SuccessionKind::Annexation)
+                .map(|x| x.date)
+                .min(),
+            valid_to: successions
+                .iter()
+                .filter(|x| x.predecessor == m.belfiore_code && x.kind != SuccessionKind::Split)
+                .map(|x| x.date)
+                .max(),
+            predecessors: successions
+                .iter()
+                .filter(|x| x.successor == m.belfiore_code)
+                .map(|x| x.predecessor.clone())
+                .collect(),
+            successors: successions
+                .iter()
+                .filter(|x| x.predecessor == m.belfiore_code)
+                .map(|x| x.successor.clone())
+                .collect(),
+        });
+    }
+    countries.sort_by(|a, b| a.belfiore_code.cmp(&b.belfiore_code));
+    countries
+}
+
+impl Belfiore {
+    /// Iterate over foreign countries, one per Z-code
+    pub fn countries(&self) -> impl Iterator<Item = &Country> {
+        self.countries.iter()
+    }
+
+    /// Obtain info for a foreign country Z-code
+    pub fn lookup_country(&self, belfiore: &str) -> Option<&Country> {
+        let belfiore = belfiore.to_uppercase();
+        self.countries
+            .binary_search_by(|x| x.belfiore_code.as_str().cmp(&belfiore))
+            .ok()
+            .map(|i| &self.countries[i])
+    }
+
+    /// Obtain info for a country from its ISO 3166-1 alpha-2 (IT) or alpha-3 (ITA) code,
+    /// as found on passports
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codice_fiscale::*;
+    ///
+    /// let germany = BELFIORE_STORE.lookup_iso("DEU").unwrap();
+    /// assert_eq!(germany.belfiore_code, "Z112");
+    ///
+    /// let place_of_birth = belfiore::Municipality::from(germany);
+    /// assert!(place_of_birth.is_foreign());
+    /// ```
+    pub fn lookup_iso(&self, iso: &str) -> Option<&Country> {
+        let iso = iso.to_uppercase();
+        self.countries.iter().find(|x| match iso.len() {
+            2 => x.iso_alpha2.as_deref() == Some(iso.as_str()),
+            3 => x.iso_alpha3.as_deref() == Some(iso.as_str()),
+            _ => false,
+        })
+    }
+
+    /// Obtain info for a Belfiore code, telling Italian municipalities from foreign countries
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codice_fiscale::*;
+    /// use codice_fiscale::country::Place;
+    ///
+    /// match BELFIORE_STORE.lookup_place("Z404") {
+    ///     Some(Place::Country(c)) => assert_eq!(c.iso_alpha2.as_deref(), Some("US")),
+    ///     _ => panic!("Z404 is a country"),
+    /// }
+    /// ```
+    pub fn lookup_place(&self, belfiore: &str) -> Option<Place<'_>> {
+        match self.lookup_country(belfiore) {
+            Some(c) => Some(Place::Country(c)),
+            None => self.lookup_belfiore(belfiore).map(Place::Municipality),
+        }
+    }
+
+    /// Iterate over the recorded changes of sovereignty between foreign countries
+    pub fn successions(&self) -> impl Iterator<Item = &Succession> {
+        self.successions.iter()
+    }
+
+    /// Returns the states which the territory of `current` belonged to at `birthdate`, that
+    /// is the Z-codes which should have been used for someone born there at that date.
+    /// There can be more than one when `current` absorbed or was formed by merging other
+    /// states and the database can't tell which one the birthplace belonged to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codice_fiscale::*;
+    /// use time::macros::date;
+    ///
+    /// let croatia = BELFIORE_STORE.lookup_iso("HR").unwrap();
+    /// let historical = BELFIORE_STORE.historical_countries(croatia, date!(1980 - 05 - 04));
+    /// assert_eq!(historical[0].name, "IUGOSLAVIA");
+    /// ```
+    pub fn historical_countries(&self, current: &Country, birthdate: Date) -> Vec<&Country> {
+        let mut found: Vec<&Country> = Vec::new();
+        self.collect_historical(&current.belfiore_code, birthdate, &mut found);
+        found
+    }
+
+    fn collect_historical<'a>(&'a self, code: &str, birthdate: Date, found: &mut Vec<&'a Country>) {
+        let country = match self.lookup_country(code) {
+            Some(c) => c,
+            None => return,
+        };
+        if country.valid_from.map_or(true, |from| birthdate >= from)
+            && !found
+                .iter()
+                .any(|x| x.belfiore_code == country.belfiore_code)
+        {
+            found.push(country);
+        }
+        for s in self
+            .successions
+            .iter()
+            .filter(|x| x.successor == country.belfiore_code && birthdate < x.date)
+        {
+            self.collect_historical(&s.predecessor, birthdate, found);
+        }
+    }
+
+    /// Returns the states which exist today in place of `historical`
+    pub fn present_day_countries(&self, historical: &Country) -> Vec<&Country> {
+        let mut found: Vec<&Country> = Vec::new();
+        self.collect_present_day(&historical.belfiore_code, &mut found);
+        found
+    }
+
+    fn collect_present_day<'a>(&'a self, code: &str, found: &mut Vec<&'a Country>) {
+        let country = match self.lookup_country(code) {
+            Some(c) => c,
+            None => return,
+        };
+        if country.valid_to.is_none()
+            && !found
+                .iter()
+                .any(|x| x.belfiore_code == country.belfiore_code)
+        {
+            found.push(country);
+        }
+        for successor in &country.successors {
+            self.collect_present_day(successor, found);
+        }
+    }
+
+    /// Returns both the historical and the present-day country for a Z-code
+    pub fn birth_country(&self, belfiore: &str) -> Option<BirthCountry<'_>> {
+        let historical = self.lookup_country(belfiore)?;
+        Some(BirthCountry {
+            historical,
+            present_day: self.present_day_countries(historical),
+        })
+    }
+}
@@ -0,0 +1,175 @@
+use crate::belfiore::{Belfiore, LoadError, Municipality};
+use crate::loader::{invalid, read_text};
+use std::collections::HashMap;
+use std::io::Read;
+
+/// Mean Earth radius, as used by the haversine formula
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// A point on Earth, in decimal degrees (WGS 84)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Coordinates {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl Coordinates {
+    /// Great-circle distance in kilometres
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codice_fiscale::geo::Coordinates;
+    ///
+    /// let rome = Coordinates { latitude: 41.8933, longitude: 12.4829 };
+    /// let milan = Coordinates { latitude: 45.4643, longitude: 9.1895 };
+    /// assert!((rome.distance_km(&milan) - 477.0).abs() < 5.0);
+    /// ```
+    pub fn distance_km(&self, other: &Coordinates) -> f64 {
+        let (lat1, lat2) = (self.latitude.to_radians(), other.latitude.to_radians());
+        let dlat = lat2 - lat1;
+        let dlon = (other.longitude - self.longitude).to_radians();
+        let a = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+    }
+}
+
+/// A nearest-municipality result
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeoMatch<'a> {
+    pub municipality: &'a Municipality,
+    pub distance_km: f64,
+}
+
+/// Parses `code,latitude,longitude` lines, sorting them by Belfiore code
+pub(crate) fn parse_coordinates(text: &str) -> Result<Vec<(String, Coordinates)>, LoadError> {
+    let mut coordinates = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        if fields.len() != 3 {
+            return Err(invalid(number + 1, "expected 3 comma separated fields"));
+        }
+        let latitude = fields[1].parse::<f64>().ok().filter(|x| x.abs() <= 90.0);
+        let longitude = fields[2].parse::<f64>().ok().filter(|x| x.abs() <= 180.0);
+        match (latitude, longitude) {
+            (Some(latitude), Some(longitude)) => coordinates.push((
+                fields[0].to_uppercase(),
+                Coordinates {
+                    latitude,
+                    longitude,
+                },
+            )),
+            _ => return Err(invalid(number + 1, "invalid coordinates")),
+        }
+    }
+    if coordinates.is_empty() {
+        return Err(LoadError::Empty);
+    }
+    coordinates.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(coordinates)
+}
+
+#[cfg(feature = "embedded-data")]
+pub(crate) fn build_coordinates() -> Vec<(String, Coordinates)> {
+    parse_coordinates(include_str!("../coordinates.txt")).unwrap()
+}
+
+/// Without the embedded data, there are no coordinates until `with_coordinates()` loads them
+#[cfg(not(feature = "embedded-data"))]
+pub(crate) fn build_coordinates() -> Vec<(String, Coordinates)> {
+    Vec::new()
+}
+
+/// Pairs the coordinates with the municipality which still exists under each code, as
+/// `lookup_belfiore()` would find it, so that `nearest()` doesn't look codes up
+pub(crate) fn locate(
+    store: &[Municipality],
+    coordinates: &[(String, Coordinates)],
+) -> Vec<(usize, Coordinates)> {
+    let mut active: HashMap<&str, usize> = HashMap::new();
+    for (i, m) in store.iter().enumerate().filter(|(_, m)| m.active) {
+        active.entry(&m.belfiore_code).or_insert(i);
+    }
+    coordinates
+        .iter()
+        .filter_map(|(code, c)| Some((*active.get(code.as_str())?, *c)))
+        .collect()
+}
+
+impl Belfiore {
+    /// Replace the embedded coordinates, the GeoNames positions of the town centres of most
+    /// existing municipalities, with your own (ie the ISTAT centroids). Lines are
+    /// `code,latitude,longitude` in decimal degrees (ie `H501,41.8933,12.4829`).
+    ///
+    /// # Errors
+    ///
+    /// * *io* - the reader failed
+    /// * *empty* - no coordinates found
+    /// * *invalid-line* - a malformed line or out of range coordinates
+    pub fn with_coordinates<R: Read>(mut self, reader: R) -> Result<Self, LoadError> {
+        self.coordinates = parse_coordinates(&read_text(reader)?)?;
+        self.located = locate(&self.store, &self.coordinates);
+        Ok(self)
+    }
+
+    /// The coordinates of a municipality, `None` if it isn't in the coordinates table
+    pub fn coordinates(&self, belfiore: &str) -> Option<Coordinates> {
+        let belfiore = belfiore.to_uppercase();
+        self.coordinates
+            .binary_search_by(|(code, _)| code.as_str().cmp(&belfiore))
+            .ok()
+            .map(|i| self.coordinates[i].1)
+    }
+
+    /// Distance in kilometres between two municipalities, `None` if either one has no
+    /// coordinates
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codice_fiscale::*;
+    ///
+    /// // Pordenone to Udine
+    /// let distance = BELFIORE_STORE.distance_km("G888", "L483").unwrap();
+    /// assert!(distance > 40.0 && distance < 50.0);
+    /// ```
+    pub fn distance_km(&self, from: &str, to: &str) -> Option<f64> {
+        Some(self.coordinates(from)?.distance_km(&self.coordinates(to)?))
+    }
+
+    /// The municipalities which still exist closest to `point`, the nearest first. At most
+    /// `limit` results are returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codice_fiscale::*;
+    /// use codice_fiscale::geo::Coordinates;
+    ///
+    /// let colosseum = Coordinates { latitude: 41.8902, longitude: 12.4922 };
+    /// let found = BELFIORE_STORE.nearest(colosseum, 1);
+    /// assert_eq!(found[0].municipality.name, "ROMA");
+    /// assert!(found[0].distance_km < 2.0);
+    /// ```
+    pub fn nearest(&self, point: Coordinates, limit: usize) -> Vec<GeoMatch<'_>> {
+        let mut found: Vec<GeoMatch> = self
+            .located
+            .iter()
+            .map(|(i, c)| GeoMatch {
+                municipality: &self.store[*i],
+                distance_km: point.distance_km(c),
+            })
+            .collect();
+        let by_distance = |a: &GeoMatch, b: &GeoMatch| a.distance_km.total_cmp(&b.distance_km);
+        if limit < found.len() {
+            found.select_nth_unstable_by(limit, by_distance);
+            found.truncate(limit);
+        }
+        found.sort_by(by_distance);
+        found
+    }
+}
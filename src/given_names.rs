@@ -0,0 +1,170 @@
+use crate::belfiore::LoadError;
+use crate::loader::{invalid, read_text};
+use crate::names::{normalize, words};
+use crate::utils::calc_name_component;
+use crate::Gender;
+use std::cmp::Reverse;
+use std::io::Read;
+
+/// A given name, with the gender it's usually given to
+#[derive(Debug, Clone, PartialEq)]
+pub struct GivenName {
+    pub name: String,
+    pub gender: Gender,
+    /// How many people have the name (ie from the ISTAT name statistics), if known
+    pub frequency: Option<u32>,
+}
+
+/// Data which is valid, but improbable enough to be worth checking
+#[derive(Debug, Clone, PartialEq)]
+pub enum Warning {
+    /// The name is only known as a name of the other gender, ie a woman named Giuseppe:
+    /// often a wrong gender, or name, was entered
+    UnusualGender { name: String, gender: Gender },
+}
+
+/// A dictionary of given names
+pub struct GivenNames {
+    /// Sorted by name, then gender, with the name code of each name
+    names: Vec<(String, GivenName)>,
+}
+
+/// Parses `name,gender[,frequency]` lines, ie `MICHELE,M` or `MICHELE,M,300`
+fn parse_given_names(text: &str) -> Result<Vec<(String, GivenName)>, LoadError> {
+    let mut names = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        if fields.len() != 2 && fields.len() != 3 {
+            return Err(invalid(
+                number + 1,
+                "expected 2 or 3 comma separated fields",
+            ));
+        }
+        let name = normalize(fields[0]);
+        if name.is_empty() {
+            return Err(invalid(number + 1, "empty name"));
+        }
+        let gender = match fields[1] {
+            "M" | "m" => Gender::M,
+            "F" | "f" => Gender::F,
+            _ => return Err(invalid(number + 1, "gender must be M or F")),
+        };
+        let frequency = match fields.get(2) {
+            Some(frequency) => Some(
+                frequency
+                    .parse()
+                    .map_err(|_| invalid(number + 1, "invalid frequency"))?,
+            ),
+            None => None,
+        };
+        names.push((
+            calc_name_component(&name),
+            GivenName {
+                name,
+                gender,
+                frequency,
+            },
+        ));
+    }
+    if names.is_empty() {
+        return Err(LoadError::Empty);
+    }
+    names.sort_by(|(_, a), (_, b)| a.name.cmp(&b.name).then(a.gender.cmp(&b.gender)));
+    Ok(names)
+}
+
+impl GivenNames {
+    /// The embedded dictionary: about 300 Italian given names
+    #[cfg(feature = "embedded-data")]
+    pub fn init() -> Self {
+        GivenNames {
+            names: parse_given_names(include_str!("../givennames.txt")).unwrap(),
+        }
+    }
+
+    /// Loads a dictionary from `name,gender` lines, ie `MICHELE,M`, optionally followed by
+    /// how many people have the name, ie `MICHELE,M,300`. A name given to both genders
+    /// gets a line for each.
+    ///
+    /// # Errors
+    ///
+    /// * *io* - the reader failed
+    /// * *empty* - no name found
+    /// * *invalid-line* - a malformed line, gender or frequency
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, LoadError> {
+        Ok(GivenNames {
+            names: parse_given_names(&read_text(reader)?)?,
+        })
+    }
+
+    /// Iterates over the names in alphabetical order
+    pub fn iter(&self) -> impl Iterator<Item = &GivenName> {
+        self.names.iter().map(|(_, n)| n)
+    }
+
+    /// The names whose code is `code` (ie MHL for Michele), optionally only the ones of a
+    /// gender. The most frequent come first, then the ones without a frequency, each in
+    /// alphabetical order; the embedded dictionary has no frequencies.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codice_fiscale::given_names::GivenNames;
+    /// use codice_fiscale::Gender;
+    ///
+    /// let names = GivenNames::from_reader("MICHELE,M,300\nMICHAEL,M,20".as_bytes()).unwrap();
+    /// let found = names.candidates("MHL", Some(Gender::M));
+    /// assert_eq!(found[0].name, "MICHELE");
+    /// assert_eq!(found[1].name, "MICHAEL");
+    /// ```
+    pub fn candidates(&self, code: &str, gender: Option<Gender>) -> Vec<&GivenName> {
+        let code = code.to_uppercase();
+        let mut found: Vec<&GivenName> = self
+            .names
+            .iter()
+            .filter(|(c, n)| *c == code && gender.map_or(true, |g| n.gender == g))
+            .map(|(_, n)| n)
+            .collect();
+        // Stable, so that equal frequencies stay in alphabetical order
+        found.sort_by_key(|n| Reverse(n.frequency));
+        found
+    }
+
+    /// The entries of a name, one for each gender it's given to
+    pub fn lookup(&self, name: &str) -> Vec<&GivenName> {
+        let name = normalize(name);
+        self.iter().filter(|n| n.name == name).collect()
+    }
+
+    /// Warns if the name is only known for the other gender. Multiple given names are
+    /// looked up as a whole, then by the first one (GIUSEPPE MARIA is a man's name); names
+    /// not in the dictionary are not reported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codice_fiscale::*;
+    ///
+    /// assert!(GIVEN_NAMES.gender_warning("Giuseppe", Gender::F).is_some());
+    /// assert!(GIVEN_NAMES.gender_warning("Giuseppe Maria", Gender::M).is_none());
+    /// assert!(GIVEN_NAMES.gender_warning("Andrea", Gender::F).is_none());
+    /// ```
+    pub fn gender_warning(&self, name: &str, gender: Gender) -> Option<Warning> {
+        let mut found = self.lookup(name);
+        if found.is_empty() {
+            let first = words(name).into_iter().next()?;
+            found = self.lookup(&first.text);
+        }
+        if found.is_empty() || found.iter().any(|n| n.gender == gender) {
+            return None;
+        }
+        Some(Warning::UnusualGender {
+            name: normalize(name),
+            gender,
+        })
+    }
+}
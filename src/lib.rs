@@ -17,11 +17,50 @@ extern crate lazy_static;
 
 /// This module contains Belfiore codes and it's used to lookup municipality info
 pub mod belfiore;
+/// This module parses birthdates written in the usual Italian formats
+pub mod birthdate;
+/// This module contains a builder for PersonData, validating each field
+pub mod builder;
+/// This module maps municipalities to their postal codes (CAP)
+#[cfg(feature = "cap")]
+pub mod cap;
+/// This module contains foreign countries (Z-codes) with their ISO 3166 codes
+pub mod country;
+/// This module contains the coordinates of municipalities, for distance and nearest queries
+#[cfg(feature = "geo")]
+pub mod geo;
+/// This module contains a dictionary of given names, with their gender
+#[cfg(feature = "given-names")]
+pub mod given_names;
+/// This module contains mergers of municipalities, to map suppressed ones to today's
+pub mod lineage;
+mod loader;
+/// This module contains the error messages, in English and Italian
+pub mod messages;
+/// This module splits and normalizes names and surnames, with their particles
+pub mod names;
+/// This module contains provinces and regions, with their ISTAT and NUTS codes
+pub mod province;
+/// This module provides prefix and typo-tolerant search over municipalities
+pub mod search;
+/// This module turns names written in other scripts into Latin letters
+pub mod transliteration;
 mod utils;
 
 use belfiore::*;
+use birthdate::BirthdateParser;
+use country::BirthCountry;
+use province::Region;
 use regex::Regex;
+use std::borrow::Cow;
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+use std::sync::Arc;
+use transliteration::is_latin;
 use utils::*;
 
 /// Error message
@@ -32,31 +71,85 @@ pub enum Error {
     InvalidName,
     InvalidSurname,
     InvalidBirthdate(Option<String>),
+    /// A two-digit year which could be of either century, with the candidate dates
+    AmbiguousBirthdate(Vec<String>),
     InvalidBelfioreCode,
+    /// A required field was not given to the builder
+    MissingField(&'static str),
+    /// No municipality or country with the given name
+    UnknownPlace(String),
+    /// Municipalities of several provinces have the given name, ie `["SAMONE (TN)", "SAMONE (TO)"]`
+    AmbiguousPlace(Vec<String>),
+    /// Neither M nor F
+    InvalidGender,
 }
 
 /// Gender enum to specify gender in PersonData struct.
 /// Italian government only accepts either male or female!
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum Gender {
     M,
     F,
 }
 
+impl TryFrom<char> for Gender {
+    type Error = Error;
+
+    /// Gender from 'M' or 'F', in either case
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'M' | 'm' => Ok(Gender::M),
+            'F' | 'f' => Ok(Gender::F),
+            _ => Err(Error::InvalidGender),
+        }
+    }
+}
+
+impl FromStr for Gender {
+    type Err = Error;
+
+    /// Gender from "M" or "F", in either case
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.trim().chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Gender::try_from(c),
+            _ => Err(Error::InvalidGender),
+        }
+    }
+}
+
+impl fmt::Display for Gender {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Gender::M => "M",
+            Gender::F => "F",
+        })
+    }
+}
+
 /// PersonData struct to pass to new() constructor for calculation of
 /// codice fiscale
 #[derive(Debug, Clone, PartialEq)]
 pub struct PersonData {
     pub name: String,
     pub surname: String,
-    /// Birthdate must be a valid YYYY-MM-AA date
+    /// Birthdate, as YYYY-MM-DD or any format accepted by `birthdate::BirthdateParser` with
+    /// a four-digit year
     pub birthdate: String,
     pub gender: Gender,
-    /// Belfiore codice for comune (ie E889). You must know it for now;
-    /// we may provide a database in the future
+    /// The municipality or foreign country of birth, as found in a `MunicipalityStore`
+    /// (ie `BELFIORE_STORE.get_info("Maniago")`). `PersonData::builder()` finds it by name,
+    /// Belfiore code or country.
     pub place_of_birth: Municipality,
 }
 
+impl PersonData {
+    /// A builder which validates each field, see `builder::PersonDataBuilder`
+    pub fn builder() -> builder::PersonDataBuilder {
+        builder::PersonDataBuilder::default()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 struct CodiceFiscaleParts {
     surname: String,
@@ -71,14 +164,91 @@ struct CodiceFiscaleParts {
 
 /// Codice fiscale calculation and parsing. The static method `check()` is most likely what you need.
 ///
-/// Note: the *PartialEq* trait here supposes every *PersonData* and *CodiceFiscaleParts* fields are equal,
-/// which actually makes for identical persons and not only identical codice fiscale.
-/// For comparison you might be better just comparing what is returned by `codice()` method.
-#[derive(Debug, PartialEq)]
+/// Note: equality, hashing and ordering only consider the codice, not the *PersonData*: a
+/// parsed codice equals the one computed from the full data of the same person.
+///
+/// # Examples
+///
+/// ```
+/// use codice_fiscale::*;
+/// use std::collections::HashSet;
+/// use std::convert::TryFrom;
+///
+/// let cf: CodiceFiscale = "BLTMHL77S04E889G".parse().unwrap();
+/// assert_eq!(cf.to_string(), "BLTMHL77S04E889G");
+///
+/// let mut seen = HashSet::new();
+/// seen.insert(cf);
+/// assert!(seen.contains(&CodiceFiscale::try_from("BLTMHL77S04E889G").unwrap()));
+/// ```
+#[derive(Debug)]
 pub struct CodiceFiscale {
     person_data: PersonData,
     codice: String,
     codice_parts: CodiceFiscaleParts,
+    dataset_version: Option<Arc<DatasetVersion>>,
+}
+
+impl PartialEq for CodiceFiscale {
+    fn eq(&self, other: &Self) -> bool {
+        self.codice == other.codice
+    }
+}
+
+impl Eq for CodiceFiscale {}
+
+impl Hash for CodiceFiscale {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.codice.hash(state);
+    }
+}
+
+impl PartialOrd for CodiceFiscale {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CodiceFiscale {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.codice.cmp(&other.codice)
+    }
+}
+
+impl fmt::Display for CodiceFiscale {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.codice)
+    }
+}
+
+#[cfg(feature = "embedded-data")]
+impl FromStr for CodiceFiscale {
+    type Err = Error;
+
+    /// Same as `CodiceFiscale::parse()`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        CodiceFiscale::parse(s)
+    }
+}
+
+#[cfg(feature = "embedded-data")]
+impl TryFrom<&str> for CodiceFiscale {
+    type Error = Error;
+
+    /// Same as `CodiceFiscale::parse()`
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        CodiceFiscale::parse(s)
+    }
+}
+
+#[cfg(feature = "embedded-data")]
+impl TryFrom<String> for CodiceFiscale {
+    type Error = Error;
+
+    /// Same as `CodiceFiscale::parse()`
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        CodiceFiscale::parse(&s)
+    }
 }
 
 const CENTURY_BASE: i32 = 2000; // This will need to be changed in 2100
@@ -87,7 +257,7 @@ const CHECKMODULI: [char; 26] = [
     'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S',
     'T', 'U', 'V', 'W', 'X', 'Y', 'Z',
 ];
-const DATE_FORMAT: &'static [time::format_description::FormatItem<'static>] = time::macros::format_description!(
+const DATE_FORMAT: &[time::format_description::FormatItem<'static>] = time::macros::format_description!(
     "[year]-[month]-[day] [hour]:[minute] [offset_hour sign:mandatory]:[offset_minute]"
 );
 lazy_static! {
@@ -131,10 +301,20 @@ lazy_static! {
         m.insert('9', (21, 9));
         m
     };
+}
+
+#[cfg(feature = "embedded-data")]
+lazy_static! {
     /// This is a static Belfiore codes DB
     pub static ref BELFIORE_STORE: Belfiore = Belfiore::init();
 }
 
+#[cfg(all(feature = "embedded-data", feature = "given-names"))]
+lazy_static! {
+    /// This is a static dictionary of common Italian given names
+    pub static ref GIVEN_NAMES: given_names::GivenNames = given_names::GivenNames::init();
+}
+
 impl CodiceFiscale {
     /// **Static** method returns true if codice fiscale is valid, false otherwise. Behind the scenes,
     /// it calls `parse()` and returns *Ok(())* in case of errors, the *Error* otherwise.
@@ -149,11 +329,21 @@ impl CodiceFiscale {
     ///     println!("Codice is OK!");
     /// }
     /// ```
+    #[cfg(feature = "embedded-data")]
     pub fn check(codice: &str) -> Result<(), Error> {
         CodiceFiscale::parse(codice)?;
         Ok(())
     }
 
+    /// Same as `check()`, looking places up in the given store instead of `BELFIORE_STORE`
+    pub fn check_with_store<S: MunicipalityStore + ?Sized>(
+        codice: &str,
+        store: &S,
+    ) -> Result<(), Error> {
+        CodiceFiscale::parse_with_store(codice, store)?;
+        Ok(())
+    }
+
     /// Constructor which creates a CodiceFiscale struct from personal data,
     /// which has to be provided as a PersonData struct
     ///
@@ -174,11 +364,77 @@ impl CodiceFiscale {
     /// }
     /// ```
     ///
+    /// Names in Cyrillic or Greek script are transliterated; for other scripts, pass the
+    /// Latin spelling of the passport (see `transliteration::Transliteration`).
+    ///
     /// # Errors
     ///
-    /// * *invalid-birthdate* - not a valid YYYY-MM-DD date
+    /// * *invalid-name*, *invalid-surname* - letters which can't be transliterated
+    /// * *invalid-birthdate* - not a valid date with a four-digit year, see
+    ///   `birthdate::BirthdateParser`
     /// * *invalid-belfiore-code* - the place was not found in the database
+    #[cfg(feature = "embedded-data")]
     pub fn new(initdata: &PersonData) -> Result<CodiceFiscale, Error> {
+        CodiceFiscale::new_with_store(initdata, &*BELFIORE_STORE)
+    }
+
+    /// Same as `new()`, also checking the name against the gender: improbable combinations,
+    /// such as a woman named Giuseppe, are returned as warnings
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codice_fiscale::*;
+    ///
+    /// let (cf, warnings) = CodiceFiscale::new_with_warnings(&PersonData {
+    ///     name           : "Giuseppe".to_string(),
+    ///     surname        : "Rossi".to_string(),
+    ///     birthdate      : "1970-01-01".to_string(),
+    ///     gender         : Gender::F,
+    ///     place_of_birth : BELFIORE_STORE.get_info("Roma").unwrap().clone(),
+    /// }).unwrap();
+    /// assert_eq!(cf.get_codice(), "RSSGPP70A41H501A");
+    /// assert_eq!(warnings.len(), 1);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// The same as `new()`
+    #[cfg(all(feature = "embedded-data", feature = "given-names"))]
+    pub fn new_with_warnings(
+        initdata: &PersonData,
+    ) -> Result<(CodiceFiscale, Vec<given_names::Warning>), Error> {
+        let cf = CodiceFiscale::new(initdata)?;
+        let warnings = GIVEN_NAMES
+            .gender_warning(&initdata.name, initdata.gender)
+            .into_iter()
+            .collect();
+        Ok((cf, warnings))
+    }
+
+    /// Same as `new()`, checking the place of birth against the given store instead of
+    /// `BELFIORE_STORE`: either a `Belfiore` database or your own `MunicipalityStore`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codice_fiscale::*;
+    /// use codice_fiscale::belfiore::Belfiore;
+    ///
+    /// let store = Belfiore::from_reader("M999,PN,NUOVO COMUNE,1".as_bytes()).unwrap();
+    /// let cf = CodiceFiscale::new_with_store(&PersonData {
+    ///     name           : "Michele".to_string(),
+    ///     surname        : "Beltrame".to_string(),
+    ///     birthdate      : "1977-11-04".to_string(),
+    ///     gender         : Gender::M,
+    ///     place_of_birth : store.lookup_belfiore("M999").unwrap().clone(),
+    /// }, &store).unwrap();
+    /// assert!(CodiceFiscale::check_with_store(cf.get_codice(), &store).is_ok());
+    /// ```
+    pub fn new_with_store<S: MunicipalityStore + ?Sized>(
+        initdata: &PersonData,
+        store: &S,
+    ) -> Result<CodiceFiscale, Error> {
         let mut cf = CodiceFiscale {
             person_data: initdata.clone(),
             codice: "".to_string(),
@@ -192,13 +448,22 @@ impl CodiceFiscale {
                 place_of_birth: Municipality::default(),
                 checkchar: '_',
             },
+            dataset_version: store.version(),
         };
 
+        // Letters of scripts without a built-in transliteration would be silently dropped
+        if !is_latin(&initdata.surname) {
+            return Err(Error::InvalidSurname);
+        }
+        if !is_latin(&initdata.name) {
+            return Err(Error::InvalidName);
+        }
+
         let mut codice = "".to_string();
         codice.push_str(cf.calc_surname());
         codice.push_str(cf.calc_name());
         codice.push_str(cf.calc_birthdate()?);
-        codice.push_str(cf.calc_belfiore()?);
+        codice.push_str(cf.calc_belfiore(store)?);
         cf.codice = codice.clone();
         codice.push(cf.calc_checkchar());
 
@@ -224,15 +489,25 @@ impl CodiceFiscale {
     /// You will usually get one of the first two errors: if the checkchar matches, it's very difficult
     /// for the codice fiscale to be incorrect, except if it was messed up on purpose.
     ///
-    /// * *invalid-length* - not 16 chars
-    /// * *invalid-checkchar* - final check char is not correct
+    /// * *invalid-length* - not 16 chars, or not all ASCII
+    /// * *invalid-checkchar* - final check char is not correct, or not all letters and digits
     /// * *invalid-surname* - not a 3-chars surname
     /// * *invalid-name* - not a 3-chars name
     /// * *invalid-birthyear*
     /// * *invalid-birthmonth*
     /// * *invalid-birthdate*
     /// * *invalid-belfiore-code*
+    #[cfg(feature = "embedded-data")]
     pub fn parse(codice: &str) -> Result<CodiceFiscale, Error> {
+        CodiceFiscale::parse_with_store(codice, &*BELFIORE_STORE)
+    }
+
+    /// Same as `parse()`, looking the place of birth up in the given store instead of
+    /// `BELFIORE_STORE`: either a `Belfiore` database or your own `MunicipalityStore`
+    pub fn parse_with_store<S: MunicipalityStore + ?Sized>(
+        codice: &str,
+        store: &S,
+    ) -> Result<CodiceFiscale, Error> {
         let mut cf = CodiceFiscale {
             person_data: PersonData {
                 name: "".to_string(),
@@ -252,12 +527,17 @@ impl CodiceFiscale {
                 place_of_birth: Municipality::default(),
                 checkchar: '_',
             },
+            dataset_version: store.version(),
         };
 
         // First off, validate CF to see if it's a valid Code
-        if codice.len() != 16 {
+        if codice.len() != 16 || !codice.is_ascii() {
             return Err(Error::InvalidLength);
         }
+        // The check char is only defined for letters and digits
+        if !codice.bytes().all(|b| b.is_ascii_alphanumeric()) {
+            return Err(Error::InvalidCheckChar);
+        }
 
         // The let's see if the check char we calculate matches
         let mut codice_nolast = codice.to_uppercase();
@@ -292,7 +572,9 @@ impl CodiceFiscale {
         // (this has implications only for parsing, not for validation, unless we stump into and unexisting Feb29)
         cf.codice_parts.birthyear = codice[6..8].to_string();
         let birthyear_num = CENTURY_BASE
-            + i32::from_str_radix(&cf.codice_parts.birthyear, 10)
+            + cf.codice_parts
+                .birthyear
+                .parse::<i32>()
                 .map_err(|_| Error::InvalidBirthdate(Some(cf.codice_parts.birthyear.clone())))?;
         let tm_now_year = time::OffsetDateTime::now_utc().year();
         let birthyear = if tm_now_year > birthyear_num {
@@ -324,8 +606,8 @@ impl CodiceFiscale {
             Err(_e) => return Err(Error::InvalidBirthdate(Some(birthdate.clone()))),
         };
 
-        cf.codice_parts.place_of_birth = match BELFIORE_STORE.lookup_belfiore(&codice[11..15]) {
-            Some(x) => x.clone(),
+        cf.codice_parts.place_of_birth = match store.lookup_belfiore(&codice[11..15]) {
+            Some(x) => x.into_owned(),
             None => return Err(Error::InvalidBelfioreCode),
         };
         cf.person_data.place_of_birth = cf.codice_parts.place_of_birth.clone();
@@ -344,6 +626,169 @@ impl CodiceFiscale {
         &self.person_data
     }
 
+    /// Returns the version of the dataset the place of birth was checked against, if the
+    /// store reports one
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codice_fiscale::*;
+    ///
+    /// let cf = CodiceFiscale::parse("BLTMHL77S04E889G").unwrap();
+    /// assert_eq!(cf.get_dataset_version(), Some(BELFIORE_STORE.version()));
+    /// ```
+    pub fn get_dataset_version(&self) -> Option<&DatasetVersion> {
+        self.dataset_version.as_deref()
+    }
+
+    /// Returns the country of birth for people born abroad, `None` for people born in Italy.
+    /// Besides the state encoded in the codice fiscale, which may no longer exist
+    /// (ie Yugoslavia), it reports the present-day states in its place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codice_fiscale::*;
+    ///
+    /// let cf = CodiceFiscale::parse("BLTMHL77S04Z118Z").unwrap();
+    /// let country = cf.get_birth_country().unwrap();
+    /// assert_eq!(country.historical.name, "IUGOSLAVIA");
+    /// assert!(country.present_day.iter().any(|c| c.name == "CROAZIA"));
+    /// ```
+    #[cfg(feature = "embedded-data")]
+    pub fn get_birth_country(&self) -> Option<BirthCountry<'static>> {
+        self.get_birth_country_with_store(&*BELFIORE_STORE)
+    }
+
+    /// Same as `get_birth_country()`, using the given store instead of `BELFIORE_STORE` (see
+    /// `MunicipalityStore::birth_country()`)
+    pub fn get_birth_country_with_store<'a, S: MunicipalityStore + ?Sized>(
+        &self,
+        store: &'a S,
+    ) -> Option<BirthCountry<'a>> {
+        store.birth_country(&self.person_data.place_of_birth.belfiore_code)
+    }
+
+    /// Returns the region of birth, `None` for people born abroad or in the territories
+    /// ceded after World War II
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codice_fiscale::*;
+    ///
+    /// let cf = CodiceFiscale::parse("BLTMHL77S04E889G").unwrap();
+    /// assert_eq!(cf.get_birth_region().unwrap().name, "Friuli-Venezia Giulia");
+    /// ```
+    pub fn get_birth_region(&self) -> Option<&'static Region> {
+        self.person_data.place_of_birth.region()
+    }
+
+    /// Returns the municipalities which exist today in place of the place of birth, which
+    /// may have been merged into another one since. Empty for people born abroad.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codice_fiscale::*;
+    ///
+    /// // Born in Crespellano, which is part of Valsamoggia since 2014
+    /// let cf = CodiceFiscale::parse("BLTMHL77S04D158H").unwrap();
+    /// let current = cf.get_present_day_places_of_birth();
+    /// assert_eq!(current[0].name, "VALSAMOGGIA");
+    /// ```
+    #[cfg(feature = "embedded-data")]
+    pub fn get_present_day_places_of_birth(&self) -> Vec<&'static Municipality> {
+        if self.person_data.place_of_birth.is_foreign() {
+            return Vec::new();
+        }
+        BELFIORE_STORE.current_successors(&self.person_data.place_of_birth.belfiore_code)
+    }
+
+    /// Same as `get_present_day_places_of_birth()`, using the given store instead of
+    /// `BELFIORE_STORE` (see `MunicipalityStore::current_successors()`)
+    pub fn get_present_day_places_of_birth_with_store<'a, S: MunicipalityStore + ?Sized>(
+        &self,
+        store: &'a S,
+    ) -> Vec<Cow<'a, Municipality>> {
+        if self.person_data.place_of_birth.is_foreign() {
+            return Vec::new();
+        }
+        store.current_successors(&self.person_data.place_of_birth.belfiore_code)
+    }
+
+    /// Suggests the given names which match the name code and the gender from the embedded
+    /// dictionary, in the order of `GivenNames::candidates()`: the embedded dictionary has no
+    /// frequencies, so they come in alphabetical order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codice_fiscale::*;
+    ///
+    /// let cf = CodiceFiscale::parse("BLTMHL77S04E889G").unwrap();
+    /// let names: Vec<&str> = cf.get_candidate_names().iter().map(|n| &*n.name).collect();
+    /// assert!(names.contains(&"MICHELE"));
+    /// assert!(names.contains(&"MICHAEL"));
+    /// assert!(!names.contains(&"MICHELA"));
+    /// ```
+    #[cfg(all(feature = "embedded-data", feature = "given-names"))]
+    pub fn get_candidate_names(&self) -> Vec<&'static given_names::GivenName> {
+        self.get_candidate_names_with_dictionary(&GIVEN_NAMES)
+    }
+
+    /// Same as `get_candidate_names()`, using the given dictionary instead of `GIVEN_NAMES`
+    #[cfg(feature = "given-names")]
+    pub fn get_candidate_names_with_dictionary<'a>(
+        &self,
+        dictionary: &'a given_names::GivenNames,
+    ) -> Vec<&'a given_names::GivenName> {
+        dictionary.candidates(&self.codice_parts.name, Some(self.person_data.gender))
+    }
+
+    /// Checks a name against this fiscal code: an error if it doesn't match the name code,
+    /// warnings if it's improbable for the gender (see `GivenNames::gender_warning()`)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codice_fiscale::*;
+    /// use codice_fiscale::given_names::Warning;
+    ///
+    /// // A woman's code
+    /// let cf = CodiceFiscale::parse("RSSMRA70A41H501W").unwrap();
+    /// assert_eq!(cf.verify_name("Maria"), Ok(vec![]));
+    /// assert_eq!(
+    ///     cf.verify_name("Mario"),
+    ///     Ok(vec![Warning::UnusualGender { name: "MARIO".to_string(), gender: Gender::F }])
+    /// );
+    /// assert_eq!(cf.verify_name("Anna"), Err(Error::InvalidName));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * *invalid-name* - the name doesn't match the name code
+    #[cfg(all(feature = "embedded-data", feature = "given-names"))]
+    pub fn verify_name(&self, name: &str) -> Result<Vec<given_names::Warning>, Error> {
+        self.verify_name_with_dictionary(name, &GIVEN_NAMES)
+    }
+
+    /// Same as `verify_name()`, using the given dictionary instead of `GIVEN_NAMES`
+    #[cfg(feature = "given-names")]
+    pub fn verify_name_with_dictionary(
+        &self,
+        name: &str,
+        dictionary: &given_names::GivenNames,
+    ) -> Result<Vec<given_names::Warning>, Error> {
+        if !self.is_name_valid(name) {
+            return Err(Error::InvalidName);
+        }
+        Ok(dictionary
+            .gender_warning(name, self.person_data.gender)
+            .into_iter()
+            .collect())
+    }
+
     /// Check if the given name is valid for this fiscal code
     pub fn is_name_valid(&self, name: &str) -> bool {
         calc_name_component(name) == self.codice_parts.name
@@ -367,15 +812,16 @@ impl CodiceFiscale {
     }
 
     fn calc_birthdate(&mut self) -> Result<&str, Error> {
-        // BIRTHDATE
-        let tm_birthdate = match time::OffsetDateTime::parse(
-            &((&self).person_data.birthdate.clone() + " 00:00 +00:00"),
-            DATE_FORMAT,
-        ) {
+        // BIRTHDATE, with a four-digit year: two-digit ones would depend on today's date
+        let tm_birthdate = match BirthdateParser::default()
+            .full_years()
+            .parse(&self.person_data.birthdate)
+        {
             Ok(v) => v,
-            Err(_e) => return Err(Error::InvalidBirthdate(None)),
+            Err(Error::InvalidBirthdate(_)) => return Err(Error::InvalidBirthdate(None)),
+            Err(e) => return Err(e),
         };
-
+        self.person_data.birthdate = tm_birthdate.to_string();
         let tm_year = tm_birthdate.year();
         self.codice_parts.birthyear = format!(
             "{:02}",
@@ -406,7 +852,13 @@ impl CodiceFiscale {
         Ok(&self.codice_parts.birthdate)
     }
 
-    fn calc_belfiore(&mut self) -> Result<&str, Error> {
+    fn calc_belfiore<S: MunicipalityStore + ?Sized>(&mut self, store: &S) -> Result<&str, Error> {
+        if store
+            .lookup_belfiore(&self.person_data.place_of_birth.belfiore_code)
+            .is_none()
+        {
+            return Err(Error::InvalidBelfioreCode);
+        }
         self.codice_parts.place_of_birth = self.person_data.place_of_birth.clone();
         Ok(&self.codice_parts.place_of_birth.belfiore_code)
     }
@@ -0,0 +1,131 @@
+use crate::belfiore::{Belfiore, Municipality};
+
+/// How a municipality ended up in another one
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum LineageKind {
+    /// Several municipalities merged into a new one, with a new Belfiore code
+    Merger,
+    /// The municipality was absorbed by an existing one, which kept its code
+    Incorporation,
+}
+
+/// A suppressed municipality and the one which took its place, from lineage.txt.
+/// Renames which kept the Belfiore code are not listed: `lookup_belfiore()` already
+/// returns the current name for them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lineage {
+    pub predecessor: String,
+    pub successor: String,
+    pub kind: LineageKind,
+}
+
+pub(crate) fn build_lineage() -> Vec<Lineage> {
+    include_str!("../lineage.txt")
+        .lines()
+        .filter(|x| !x.is_empty())
+        .map(|x| x.split(',').collect::<Vec<&str>>())
+        .map(|x| Lineage {
+            predecessor: x[0].to_owned(),
+            successor: x[1].to_owned(),
+            kind: match x[2] {
+                "incorporation" => LineageKind::Incorporation,
+                _ => LineageKind::Merger,
+            },
+        })
+        .collect()
+}
+
+impl Belfiore {
+    /// Iterate over the recorded mergers and incorporations
+    pub fn lineage(&self) -> impl Iterator<Item = &Lineage> {
+        self.lineage.iter()
+    }
+
+    /// Resolve a Belfiore code, possibly of a municipality which no longer exists, to the
+    /// municipalities which exist today in its place. A municipality which still exists
+    /// resolves to itself; an empty result means the code is unknown or its fate isn't
+    /// recorded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codice_fiscale::*;
+    ///
+    /// // Bazzano merged into Valsamoggia in 2014
+    /// let bazzano = BELFIORE_STORE.get_info("Bazzano").unwrap();
+    /// let current = BELFIORE_STORE.current_successors(&bazzano.belfiore_code);
+    /// assert_eq!(current[0].name, "VALSAMOGGIA");
+    /// ```
+    pub fn current_successors(&self, belfiore: &str) -> Vec<&Municipality> {
+        let mut found = Vec::new();
+        let mut visited = Vec::new();
+        self.collect_successors(&belfiore.to_uppercase(), &mut visited, &mut found);
+        found
+    }
+
+    fn collect_successors<'a>(
+        &'a self,
+        belfiore: &str,
+        visited: &mut Vec<String>,
+        found: &mut Vec<&'a Municipality>,
+    ) {
+        if visited.iter().any(|x| x == belfiore) {
+            return;
+        }
+        visited.push(belfiore.to_owned());
+        match self.lookup_belfiore(belfiore) {
+            Some(m) if m.active => found.push(m),
+            Some(_) => {
+                for l in self.lineage.iter().filter(|x| x.predecessor == belfiore) {
+                    self.collect_successors(&l.successor, visited, found);
+                }
+            }
+            None => (),
+        }
+    }
+
+    /// The municipalities which merged into, or were absorbed by, the given one, directly
+    /// or through intermediate mergers
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codice_fiscale::*;
+    ///
+    /// let valsamoggia = BELFIORE_STORE.get_info("Valsamoggia").unwrap();
+    /// let predecessors = BELFIORE_STORE.predecessors(&valsamoggia.belfiore_code);
+    /// assert!(predecessors.iter().any(|m| m.name == "CRESPELLANO"));
+    /// ```
+    pub fn predecessors(&self, belfiore: &str) -> Vec<&Municipality> {
+        let mut found: Vec<&Municipality> = Vec::new();
+        let mut pending = vec![belfiore.to_uppercase()];
+        while let Some(code) = pending.pop() {
+            let province = match self.lookup_belfiore(&code) {
+                Some(m) => m.province.clone(),
+                None => continue,
+            };
+            for l in self.lineage.iter().filter(|x| x.successor == code) {
+                if let Some(m) = self.suppressed(&l.predecessor, &province) {
+                    if !found.iter().any(|x| x.belfiore_code == m.belfiore_code) {
+                        found.push(m);
+                        pending.push(l.predecessor.clone());
+                    }
+                }
+            }
+        }
+        found
+    }
+
+    // A few codes were reused after the municipality was suppressed (ie B385, both Calavino
+    // and Calatafimi-Segesta): the suppressed entry is looked for in the successor's province
+    fn suppressed(&self, belfiore: &str, province: &str) -> Option<&Municipality> {
+        let mut found = self
+            .iter()
+            .filter(|x| x.belfiore_code == belfiore && !x.active);
+        let first = found.next()?;
+        if first.province == province {
+            return Some(first);
+        }
+        found.find(|x| x.province == province).or(Some(first))
+    }
+}
@@ -0,0 +1,309 @@
+use crate::belfiore::{AltName, Language, LoadError, Municipality, FOREIGN_PROVINCE};
+use regex::Regex;
+#[cfg(feature = "embedded-data")]
+use std::borrow::Cow;
+use std::io::Read;
+
+lazy_static! {
+    static ref BELFIORE_CODE: Regex = Regex::new("^[A-Z][0-9]{3}$").unwrap();
+    static ref PROVINCE_CODE: Regex = Regex::new("^[A-Z]{2}$").unwrap();
+    static ref ISTAT_CODE: Regex = Regex::new("^[0-9]{6}$").unwrap();
+    static ref ISTAT_COUNTRY_CODE: Regex = Regex::new("^[0-9]{3}$").unwrap();
+}
+
+#[cfg(feature = "embedded-data")]
+lazy_static! {
+    /// The records packed by build.rs, decompressed once for every `Belfiore::init()`
+    pub(crate) static ref EMBEDDED: Vec<u8> = miniz_oxide::inflate::decompress_to_vec(
+        include_bytes!(concat!(env!("OUT_DIR"), "/belfiore.bin"))
+    )
+    .expect("embedded data is deflate-compressed");
+}
+
+// Column headers of the supported official exports: ISTAT "Elenco comuni italiani",
+// ISTAT "Elenco codici e denominazioni delle unità territoriali estere", the ANPR
+// "archivio comuni" and the Agenzia delle Entrate table of codici catastali saved as CSV,
+// the last two also listing suppressed municipalities
+const CODE_HEADERS: &[&str] = &[
+    "CODICE CATASTALE DEL COMUNE",
+    "CODCATASTALE",
+    "CODICE AT",
+    "CODICE CATASTALE",
+];
+const NAME_HEADERS: &[&str] = &[
+    "DENOMINAZIONE IN ITALIANO",
+    "DENOMINAZIONE_IT",
+    "DENOMINAZIONE IT",
+    "DENOMINAZIONE",
+];
+const PROVINCE_HEADERS: &[&str] = &["SIGLA AUTOMOBILISTICA", "SIGLAPROVINCIA", "PROVINCIA"];
+const ISTAT_HEADERS: &[&str] = &[
+    "CODICE COMUNE FORMATO ALFANUMERICO",
+    "CODISTAT",
+    "CODICE ISTAT",
+];
+const ALT_NAME_HEADERS: &[&str] = &["DENOMINAZIONE ALTRA LINGUA", "ALTRADENOMINAZIONE"];
+// Italian and other name together, ie "Bolzano/Bozen" or "Duino Aurisina-Devin Nabrežina"
+const BILINGUAL_NAME_HEADERS: &[&str] = &["DENOMINAZIONE (ITALIANA E STRANIERA)"];
+const STATUS_HEADERS: &[&str] = &["STATO"];
+const END_DATE_HEADERS: &[&str] = &["DATACESSAZIONE", "DATA FINE VALIDITA"];
+
+/// Reads everything the reader has
+pub(crate) fn read_bytes<R: Read>(mut reader: R) -> Result<Vec<u8>, LoadError> {
+    let mut bytes = Vec::new();
+    reader
+        .read_to_end(&mut bytes)
+        .map_err(|e| LoadError::Io(e.to_string()))?;
+    Ok(bytes)
+}
+
+/// Reads everything the reader has as text, see `decode()`
+#[cfg(any(feature = "cap", feature = "geo", feature = "given-names"))]
+pub(crate) fn read_text<R: Read>(reader: R) -> Result<String, LoadError> {
+    read_bytes(reader).map(|bytes| decode(&bytes))
+}
+
+/// Decodes UTF-8, falling back to Latin-1 which the ISTAT exports use
+pub(crate) fn decode(bytes: &[u8]) -> String {
+    let text = match std::str::from_utf8(bytes) {
+        Ok(s) => s.to_owned(),
+        Err(_) => bytes.iter().map(|&b| b as char).collect(),
+    };
+    text.trim_start_matches('\u{feff}').to_owned()
+}
+
+/// Decodes the records packed by build.rs, borrowing every text field from the blob
+#[cfg(feature = "embedded-data")]
+pub(crate) fn parse_binary(blob: &'static [u8]) -> Vec<Municipality> {
+    let text = |bytes: &'static [u8]| -> Cow<'static, str> {
+        Cow::Borrowed(std::str::from_utf8(bytes).expect("embedded data is UTF-8"))
+    };
+    let mut db = Vec::new();
+    let mut rest = blob
+        .strip_prefix(b"BLF1")
+        .expect("unknown embedded data format");
+    while !rest.is_empty() {
+        let (name_len, istat_len) = (usize::from(rest[7]), usize::from(rest[8]));
+        let (record, tail) = rest.split_at(9 + name_len + istat_len);
+        db.push(Municipality {
+            name: text(&record[9..9 + name_len]),
+            province: text(&record[4..6]),
+            belfiore_code: text(&record[0..4]),
+            active: record[6] & 1 == 1,
+            istat_code: Some(&record[9 + name_len..])
+                .filter(|x| !x.is_empty())
+                .map(text),
+            alt_names: Vec::new(),
+        });
+        rest = tail;
+    }
+    db
+}
+
+/// Parses either the belfiore.txt format (code,province,name,active[,istat] without header)
+/// or one of the official exports, recognized by their header
+pub(crate) fn parse_dataset(text: &str) -> Result<Vec<Municipality>, LoadError> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(i, x)| (i + 1, x.trim_end_matches('\r')))
+        .filter(|(_, x)| !x.trim().is_empty());
+    let (first_number, first) = lines.next().ok_or(LoadError::Empty)?;
+    let delimiter = if first.contains(';') { ';' } else { ',' };
+    let header: Vec<String> = split_record(first, delimiter)
+        .iter()
+        .map(|x| x.trim().to_uppercase())
+        .collect();
+
+    let db = match find_column(&header, CODE_HEADERS) {
+        Some(code) => parse_export(&header, code, delimiter, lines)?,
+        None => std::iter::once((first_number, first))
+            .chain(lines)
+            .map(|(number, line)| parse_native(number, line))
+            .collect::<Result<Vec<Municipality>, LoadError>>()?,
+    };
+    if db.is_empty() {
+        return Err(LoadError::Empty);
+    }
+    Ok(db)
+}
+
+fn parse_native(number: usize, line: &str) -> Result<Municipality, LoadError> {
+    let fields: Vec<&str> = line.split(',').collect();
+    if fields.len() != 4 && fields.len() != 5 {
+        return Err(invalid(number, "expected 4 or 5 comma separated fields"));
+    }
+    let active = match fields[3] {
+        "1" => true,
+        "0" => false,
+        _ => return Err(invalid(number, "active flag must be 0 or 1")),
+    };
+    validate(
+        number,
+        Municipality {
+            name: fields[2].to_owned().into(),
+            province: fields[1].to_owned().into(),
+            belfiore_code: fields[0].to_owned().into(),
+            active,
+            istat_code: fields
+                .get(4)
+                .filter(|x| !x.is_empty())
+                .map(|x| x.to_string().into()),
+            alt_names: Vec::new(),
+        },
+    )
+}
+
+fn parse_export<'a>(
+    header: &[String],
+    code: usize,
+    delimiter: char,
+    lines: impl Iterator<Item = (usize, &'a str)>,
+) -> Result<Vec<Municipality>, LoadError> {
+    let name = find_column(header, NAME_HEADERS).ok_or(LoadError::UnknownFormat)?;
+    let province = find_column(header, PROVINCE_HEADERS);
+    let istat = find_column(header, ISTAT_HEADERS);
+    let alt_name = find_column(header, ALT_NAME_HEADERS);
+    let bilingual_name = find_column(header, BILINGUAL_NAME_HEADERS);
+    let status = find_column(header, STATUS_HEADERS);
+    let end_date = find_column(header, END_DATE_HEADERS);
+
+    let mut db = Vec::new();
+    for (number, line) in lines {
+        let fields = split_record(line, delimiter);
+        let field = |i: usize| fields.get(i).map_or("", |x| x.trim());
+        if fields.len() < header.len() {
+            return Err(invalid(number, "fewer fields than the header"));
+        }
+        let belfiore_code = field(code).to_uppercase();
+        // Foreign territories without a code of their own (ie Antarctica) are skipped
+        if belfiore_code.is_empty() || belfiore_code == "N.D." {
+            continue;
+        }
+        let province_code = match province {
+            Some(p) => field(p).to_uppercase(),
+            None if belfiore_code.starts_with('Z') => FOREIGN_PROVINCE.to_string(),
+            None => return Err(invalid(number, "missing province")),
+        };
+        // Lists without status only contain municipalities which exist today
+        let active = match (status, end_date) {
+            (Some(s), _) => field(s).eq_ignore_ascii_case("A"),
+            // 9999-12-31 (ANPR) or 31/12/9999 (Agenzia delle Entrate) for no end
+            (None, Some(e)) => field(e).is_empty() || field(e).contains("9999"),
+            (None, None) => !belfiore_code.starts_with('Z'),
+        };
+        let italian_name = field(name).to_uppercase();
+        let other_name = match (alt_name, bilingual_name) {
+            (Some(a), _) => field(a).to_uppercase(),
+            (None, Some(b)) => split_bilingual(&field(b).to_uppercase(), &italian_name),
+            (None, None) => String::new(),
+        };
+        let alt_names = match language_of(&province_code) {
+            Some(language) if !other_name.is_empty() && other_name != italian_name => {
+                vec![AltName {
+                    language,
+                    name: other_name.into(),
+                }]
+            }
+            _ => Vec::new(),
+        };
+        db.push(validate(
+            number,
+            Municipality {
+                name: italian_name.into(),
+                province: province_code.into(),
+                belfiore_code: belfiore_code.into(),
+                active,
+                istat_code: istat
+                    .map(field)
+                    .filter(|x| !x.is_empty())
+                    .map(|x| x.to_owned().into()),
+                alt_names,
+            },
+        )?);
+    }
+    Ok(db)
+}
+
+/// The other name in a bilingual one, which starts with the Italian name
+fn split_bilingual(bilingual: &str, italian: &str) -> String {
+    match bilingual.strip_prefix(italian) {
+        Some(rest) => rest.trim_start_matches(&['/', '-', ' '][..]).to_owned(),
+        None => String::new(),
+    }
+}
+
+/// The language of the names other than the Italian one in a province
+fn language_of(province: &str) -> Option<Language> {
+    match province {
+        "BZ" => Some(Language::German),
+        "AO" => Some(Language::French),
+        "TS" | "GO" | "UD" => Some(Language::Slovenian),
+        _ => None,
+    }
+}
+
+fn validate(number: usize, municipality: Municipality) -> Result<Municipality, LoadError> {
+    if !BELFIORE_CODE.is_match(&municipality.belfiore_code) {
+        return Err(invalid(number, "invalid Belfiore code"));
+    }
+    if !PROVINCE_CODE.is_match(&municipality.province) {
+        return Err(invalid(number, "invalid province"));
+    }
+    if municipality.name.is_empty() {
+        return Err(invalid(number, "empty name"));
+    }
+    if municipality.belfiore_code.starts_with('Z') != municipality.is_foreign() {
+        return Err(invalid(
+            number,
+            "Z-codes and only Z-codes must have province EE",
+        ));
+    }
+    let istat_code = if municipality.is_foreign() {
+        &*ISTAT_COUNTRY_CODE
+    } else {
+        &*ISTAT_CODE
+    };
+    if let Some(istat) = &municipality.istat_code {
+        if !istat_code.is_match(istat) {
+            return Err(invalid(number, "invalid ISTAT code"));
+        }
+    }
+    Ok(municipality)
+}
+
+pub(crate) fn invalid(line: usize, reason: &str) -> LoadError {
+    LoadError::InvalidLine {
+        line,
+        reason: reason.to_owned(),
+    }
+}
+
+/// The first of the given columns the header has, so that the generic names of the
+/// Agenzia delle Entrate table only match when the specific ones are missing
+fn find_column(header: &[String], names: &[&str]) -> Option<usize> {
+    names
+        .iter()
+        .find_map(|name| header.iter().position(|x| x == name))
+}
+
+/// Splits a CSV record, honouring double quotes
+fn split_record(line: &str, delimiter: char) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            c if c == delimiter && !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
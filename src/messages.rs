@@ -0,0 +1,120 @@
+use crate::Error;
+use std::fmt;
+
+/// Language of the error messages
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Locale {
+    English,
+    Italian,
+}
+
+impl Locale {
+    /// Locale from its ISO 639 code (ie it), or a language tag (it-IT)
+    pub fn from_code(code: &str) -> Option<Locale> {
+        let language = code.split(['-', '_']).next()?;
+        match language.to_lowercase().as_str() {
+            "en" => Some(Locale::English),
+            "it" => Some(Locale::Italian),
+            _ => None,
+        }
+    }
+}
+
+/// "A, B or C", "A, B o C"
+fn alternatives(items: &[String], or: &str) -> String {
+    match items.split_last() {
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} {} {}", rest.join(", "), or, last),
+        None => String::new(),
+    }
+}
+
+fn field_name(field: &str, locale: Locale) -> &str {
+    match (locale, field) {
+        (Locale::Italian, "name") => "nome",
+        (Locale::Italian, "surname") => "cognome",
+        (Locale::Italian, "birthdate") => "data di nascita",
+        (Locale::Italian, "gender") => "sesso",
+        (Locale::Italian, "birthplace") => "luogo di nascita",
+        (_, field) => field,
+    }
+}
+
+impl Error {
+    /// A message to show to users, in the given language
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codice_fiscale::*;
+    /// use codice_fiscale::messages::Locale;
+    ///
+    /// let e = CodiceFiscale::parse("BLTMHL77S04E889Y").unwrap_err();
+    /// assert_eq!(e.message(Locale::Italian), "Carattere di controllo non valido");
+    /// assert_eq!(e.to_string(), "Invalid check character");
+    /// ```
+    pub fn message(&self, locale: Locale) -> String {
+        use Locale::*;
+        match (self, locale) {
+            (Error::InvalidLength, English) => {
+                "Invalid length: a codice fiscale has 16 characters".to_owned()
+            }
+            (Error::InvalidLength, Italian) => {
+                "Lunghezza non valida: il codice fiscale ha 16 caratteri".to_owned()
+            }
+            (Error::InvalidCheckChar, English) => "Invalid check character".to_owned(),
+            (Error::InvalidCheckChar, Italian) => "Carattere di controllo non valido".to_owned(),
+            (Error::InvalidName, English) => "Invalid name".to_owned(),
+            (Error::InvalidName, Italian) => "Nome non valido".to_owned(),
+            (Error::InvalidSurname, English) => "Invalid surname".to_owned(),
+            (Error::InvalidSurname, Italian) => "Cognome non valido".to_owned(),
+            (Error::InvalidBirthdate(None), English) => "Invalid birthdate".to_owned(),
+            (Error::InvalidBirthdate(None), Italian) => "Data di nascita non valida".to_owned(),
+            (Error::InvalidBirthdate(Some(date)), English) => {
+                format!("Invalid birthdate: {}", date)
+            }
+            (Error::InvalidBirthdate(Some(date)), Italian) => {
+                format!("Data di nascita non valida: {}", date)
+            }
+            (Error::AmbiguousBirthdate(dates), English) => format!(
+                "Ambiguous birthdate, it could be {}",
+                alternatives(dates, "or")
+            ),
+            (Error::AmbiguousBirthdate(dates), Italian) => format!(
+                "Data di nascita ambigua, potrebbe essere {}",
+                alternatives(dates, "o")
+            ),
+            (Error::InvalidBelfioreCode, English) => "Unknown Belfiore code".to_owned(),
+            (Error::InvalidBelfioreCode, Italian) => {
+                "Codice catastale (Belfiore) sconosciuto".to_owned()
+            }
+            (Error::MissingField(field), English) => format!("Missing {}", field),
+            (Error::MissingField(field), Italian) => {
+                format!("Manca il campo {}", field_name(field, locale))
+            }
+            (Error::UnknownPlace(place), English) => format!("Unknown place of birth: {}", place),
+            (Error::UnknownPlace(place), Italian) => {
+                format!("Luogo di nascita sconosciuto: {}", place)
+            }
+            (Error::AmbiguousPlace(places), English) => format!(
+                "Ambiguous place of birth, specify the province: {}",
+                alternatives(places, "or")
+            ),
+            (Error::AmbiguousPlace(places), Italian) => format!(
+                "Luogo di nascita ambiguo, specificare la provincia: {}",
+                alternatives(places, "o")
+            ),
+            (Error::InvalidGender, English) => "Invalid gender: it must be M or F".to_owned(),
+            (Error::InvalidGender, Italian) => "Sesso non valido: deve essere M o F".to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    /// The English message, see `message()` for the other languages
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message(Locale::English))
+    }
+}
+
+impl std::error::Error for Error {}
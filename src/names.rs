@@ -0,0 +1,136 @@
+use crate::utils::to_base_letters;
+pub use crate::utils::{trace_name_component, trace_surname_component, ComponentTrace};
+
+/// Particles of surnames, such as the DE of DE LUCA or the D' of D'ANGELO
+pub const PARTICLES: &[&str] = &[
+    "D", "DA", "DAGLI", "DAI", "DAL", "DALL", "DALLA", "DALLE", "DAS", "DE", "DEGLI", "DEI", "DEL",
+    "DELL", "DELLA", "DELLE", "DEN", "DER", "DI", "DOS", "DU", "L", "LA", "LE", "LI", "LO", "VAN",
+    "VON",
+];
+
+/// What separates a word of a name from the next one
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Separator {
+    Space,
+    Hyphen,
+    Apostrophe,
+}
+
+/// A word of a name or surname
+#[derive(Clone, Debug, PartialEq)]
+pub struct Word {
+    /// Uppercase, without diacritics and separators
+    pub text: String,
+    /// True for particles (DE, DELLA, D', LO...) followed by other words
+    pub particle: bool,
+    /// None for the last word
+    pub separator: Option<Separator>,
+}
+
+fn separator(c: char) -> Option<Separator> {
+    match c {
+        '\'' | '’' | '‘' | '`' | '´' | 'ʼ' => Some(Separator::Apostrophe),
+        '-' | '‐' | '‑' | '–' | '—' => Some(Separator::Hyphen),
+        c if c.is_whitespace() => Some(Separator::Space),
+        _ => None,
+    }
+}
+
+/// Splits a name or surname into its words. Apostrophes and hyphens separate words like
+/// spaces do; letters are transliterated and diacritics removed, other characters dropped.
+///
+/// # Examples
+///
+/// ```
+/// use codice_fiscale::names::*;
+///
+/// let parts = words("D’Angelo");
+/// assert_eq!(parts[0].text, "D");
+/// assert!(parts[0].particle);
+/// assert_eq!(parts[0].separator, Some(Separator::Apostrophe));
+/// assert_eq!(parts[1].text, "ANGELO");
+/// // A particle alone is the surname itself
+/// assert!(!words("Lo")[0].particle);
+/// ```
+pub fn words(name: &str) -> Vec<Word> {
+    let mut words: Vec<Word> = Vec::new();
+    let mut text = String::new();
+    for c in to_base_letters(name).chars() {
+        match separator(c) {
+            Some(sep) => {
+                if !text.is_empty() {
+                    words.push(Word {
+                        text: std::mem::take(&mut text),
+                        particle: false,
+                        separator: Some(sep),
+                    });
+                } else if let Some(last) = words.last_mut() {
+                    // "D' Angelo", "Rossi - Bianchi": the strongest separator wins
+                    if sep != Separator::Space {
+                        last.separator = Some(sep);
+                    }
+                }
+            }
+            None if c.is_ascii_alphabetic() => text.push(c),
+            None => (),
+        }
+    }
+    if !text.is_empty() {
+        words.push(Word {
+            text,
+            particle: false,
+            separator: None,
+        });
+    } else if let Some(last) = words.last_mut() {
+        last.separator = None;
+    }
+    let count = words.len();
+    for word in words.iter_mut().take(count.saturating_sub(1)) {
+        word.particle = PARTICLES.contains(&&*word.text);
+    }
+    words
+}
+
+/// The canonical spelling of a name or surname: uppercase, without diacritics, words
+/// separated by a single space, hyphen or apostrophe
+///
+/// # Examples
+///
+/// ```
+/// use codice_fiscale::names::normalize;
+///
+/// assert_eq!(normalize("  d’ Angelo "), "D'ANGELO");
+/// assert_eq!(normalize("Gian-Luca"), "GIAN-LUCA");
+/// assert_eq!(normalize("Nicolò   Maria"), "NICOLO MARIA");
+/// ```
+pub fn normalize(name: &str) -> String {
+    let mut normalized = String::with_capacity(name.len());
+    for word in words(name) {
+        normalized.push_str(&word.text);
+        normalized.push_str(match word.separator {
+            Some(Separator::Space) => " ",
+            Some(Separator::Hyphen) => "-",
+            Some(Separator::Apostrophe) => "'",
+            None => "",
+        });
+    }
+    normalized
+}
+
+/// The letters the surname and name codes are computed from.
+///
+/// As the official rules require, compound surnames are taken as a whole, particles
+/// included (DE LUCA is DELUCA, D'ANGELO is DANGELO), and so are multiple given names
+/// (MARIA GRAZIA is MARIAGRAZIA); spaces, apostrophes and hyphens are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use codice_fiscale::names::letters;
+///
+/// assert_eq!(letters("Della Valle"), "DELLAVALLE");
+/// assert_eq!(letters("Gian-Luca"), "GIANLUCA");
+/// ```
+pub fn letters(name: &str) -> String {
+    words(name).into_iter().map(|w| w.text).collect()
+}
@@ -0,0 +1,156 @@
+use crate::belfiore::{Belfiore, Municipality};
+
+/// An Italian region
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Region {
+    /// Two-digit ISTAT code (ie 06)
+    pub istat_code: &'static str,
+    pub name: &'static str,
+    /// NUTS-2 code (ie ITH4). Trentino-Alto Adige has none: its provinces are NUTS-2 units
+    pub nuts2: Option<&'static str>,
+}
+
+static REGIONS: [Region; 20] = [
+    region("01", "Piemonte", Some("ITC1")),
+    region("02", "Valle d'Aosta", Some("ITC2")),
+    region("03", "Lombardia", Some("ITC4")),
+    region("04", "Trentino-Alto Adige", None),
+    region("05", "Veneto", Some("ITH3")),
+    region("06", "Friuli-Venezia Giulia", Some("ITH4")),
+    region("07", "Liguria", Some("ITC3")),
+    region("08", "Emilia-Romagna", Some("ITH5")),
+    region("09", "Toscana", Some("ITI1")),
+    region("10", "Umbria", Some("ITI2")),
+    region("11", "Marche", Some("ITI3")),
+    region("12", "Lazio", Some("ITI4")),
+    region("13", "Abruzzo", Some("ITF1")),
+    region("14", "Molise", Some("ITF2")),
+    region("15", "Campania", Some("ITF3")),
+    region("16", "Puglia", Some("ITF4")),
+    region("17", "Basilicata", Some("ITF5")),
+    region("18", "Calabria", Some("ITF6")),
+    region("19", "Sicilia", Some("ITG1")),
+    region("20", "Sardegna", Some("ITG2")),
+];
+
+const fn region(
+    istat_code: &'static str,
+    name: &'static str,
+    nuts2: Option<&'static str>,
+) -> Region {
+    Region {
+        istat_code,
+        name,
+        nuts2,
+    }
+}
+
+/// An Italian province, including the ones which have been abolished or re-coded
+#[derive(Debug, Clone, PartialEq)]
+pub struct Province {
+    /// Two-letter code (ie PN), as used in `Municipality::province`
+    pub code: String,
+    pub name: String,
+    /// Three-digit ISTAT code (ie 093), missing for the provinces ceded after World War II
+    pub istat_code: Option<String>,
+    pub region: Option<&'static Region>,
+    /// NUTS-3 code (ie ITH41), missing for some historical provinces
+    pub nuts3: Option<String>,
+    /// Whether the province still exists today
+    pub active: bool,
+    /// Codes of the provinces which took the place of an abolished or re-coded one
+    pub successors: Vec<String>,
+}
+
+impl Province {
+    /// NUTS-2 code: the region's, except in Trentino-Alto Adige where provinces are NUTS-2 units
+    pub fn nuts2(&self) -> Option<&str> {
+        self.nuts3.as_ref().map(|x| &x[..4])
+    }
+}
+
+lazy_static! {
+    static ref PROVINCES: Vec<Province> = include_str!("../provinces.txt")
+        .lines()
+        .filter(|x| !x.is_empty())
+        .map(|x| x.split(',').collect::<Vec<&str>>())
+        .map(|x| Province {
+            code: x[0].to_owned(),
+            name: x[2].to_owned(),
+            istat_code: Some(x[1].to_owned()).filter(|x| !x.is_empty()),
+            region: REGIONS.iter().find(|r| r.istat_code == x[3]),
+            nuts3: Some(x[4].to_owned()).filter(|x| !x.is_empty()),
+            active: x[5] == "1",
+            successors: x[6].split_whitespace().map(str::to_owned).collect(),
+        })
+        .collect();
+}
+
+/// Iterate over the regions
+pub fn regions() -> impl Iterator<Item = &'static Region> {
+    REGIONS.iter()
+}
+
+/// Iterate over the provinces, including abolished ones
+pub fn provinces() -> impl Iterator<Item = &'static Province> {
+    PROVINCES.iter()
+}
+
+/// Obtain info for a two-letter province code
+///
+/// # Examples
+///
+/// ```
+/// use codice_fiscale::province;
+///
+/// let pn = province::lookup_province("PN").unwrap();
+/// assert_eq!(pn.name, "Pordenone");
+/// assert_eq!(pn.region.unwrap().name, "Friuli-Venezia Giulia");
+/// assert_eq!(pn.nuts3.as_deref(), Some("ITH41"));
+/// ```
+pub fn lookup_province(code: &str) -> Option<&'static Province> {
+    PROVINCES.iter().find(|x| x.code.eq_ignore_ascii_case(code))
+}
+
+/// Obtain info for a region name or two-digit ISTAT code
+pub fn lookup_region(region: &str) -> Option<&'static Region> {
+    REGIONS
+        .iter()
+        .find(|x| x.istat_code == region || x.name.eq_ignore_ascii_case(region))
+}
+
+/// Iterate over the provinces of a region, including abolished ones
+pub fn provinces_in(region: &'static Region) -> impl Iterator<Item = &'static Province> {
+    PROVINCES.iter().filter(move |x| x.region == Some(region))
+}
+
+impl Municipality {
+    /// The province of this municipality, `None` for foreign countries
+    pub fn province_info(&self) -> Option<&'static Province> {
+        lookup_province(&self.province).filter(|_| !self.is_foreign())
+    }
+
+    /// The region of this municipality, `None` for foreign countries and for the
+    /// territories ceded after World War II
+    pub fn region(&self) -> Option<&'static Region> {
+        self.province_info()?.region
+    }
+}
+
+impl Belfiore {
+    /// Iterate over the municipalities of a region, including the ones of its abolished provinces
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codice_fiscale::*;
+    ///
+    /// let molise = province::lookup_region("Molise").unwrap();
+    /// assert!(BELFIORE_STORE.by_region(molise).any(|m| m.name == "ISERNIA"));
+    /// ```
+    pub fn by_region<'a>(&'a self, region: &'a Region) -> impl Iterator<Item = &'a Municipality> {
+        self.store
+            .iter()
+            .filter(move |x| x.region() == Some(region))
+    }
+}
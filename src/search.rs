@@ -0,0 +1,276 @@
+use crate::belfiore::{Belfiore, Municipality};
+use crate::utils::to_base_letters;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+/// Filters applied to search results. Every `None` field matches anything.
+///
+/// # Examples
+///
+/// ```
+/// use codice_fiscale::search::SearchFilter;
+///
+/// let filter = SearchFilter {
+///     province: Some("PN".to_string()),
+///     active: Some(true),
+/// };
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchFilter {
+    /// Two-letter province code (ie PN)
+    pub province: Option<String>,
+    /// Only municipalities which still exist (or only suppressed ones)
+    pub active: Option<bool>,
+}
+
+impl SearchFilter {
+    fn matches(&self, municipality: &Municipality) -> bool {
+        self.province
+            .as_ref()
+            .map_or(true, |p| p.eq_ignore_ascii_case(&municipality.province))
+            && self.active.map_or(true, |a| a == municipality.active)
+    }
+}
+
+/// A fuzzy search result
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchMatch<'a> {
+    pub municipality: &'a Municipality,
+    /// Edit distance between the query and the closest prefix of the name
+    pub distance: usize,
+}
+
+/// Names sorted in normalized form, so that names sharing a prefix are
+/// adjacent: prefix search is a binary search, and fuzzy search can walk
+/// the list as if it were a trie. The normalized names are kept together in
+/// one string rather than allocated one by one.
+pub(crate) struct SearchIndex {
+    names: String,
+    /// Start and end of the normalized name in `names`, and the municipality
+    entries: Vec<(usize, usize, usize)>,
+}
+
+impl SearchIndex {
+    pub(crate) fn new(store: &[Municipality]) -> Self {
+        let mut names = String::new();
+        let mut entries = Vec::new();
+        for (i, municipality) in store.iter().enumerate() {
+            for name in municipality.names() {
+                let start = names.len();
+                normalize_into(name, &mut names);
+                entries.push((start, names.len(), i));
+            }
+        }
+        entries.sort_by(|a, b| names[a.0..a.1].cmp(&names[b.0..b.1]).then(a.2.cmp(&b.2)));
+        entries.dedup_by(|a, b| names[a.0..a.1] == names[b.0..b.1] && a.2 == b.2);
+        Self { names, entries }
+    }
+
+    fn key(&self, entry: &(usize, usize, usize)) -> &str {
+        &self.names[entry.0..entry.1]
+    }
+
+    /// The municipalities with exactly the given normalized name
+    pub(crate) fn find<'a>(&'a self, normalized: &'a str) -> impl Iterator<Item = usize> + 'a {
+        let start = self
+            .entries
+            .partition_point(|entry| self.key(entry) < normalized);
+        self.entries[start..]
+            .iter()
+            .take_while(move |entry| self.key(entry) == normalized)
+            .map(|entry| entry.2)
+    }
+}
+
+/// Uppercases, folds diacritics (both "à" and the "A'" spelling used in the
+/// database) and treats apostrophes, hyphens and dots as spaces
+pub(crate) fn normalize(name: &str) -> String {
+    let mut normalized = String::with_capacity(name.len());
+    normalize_into(name, &mut normalized);
+    normalized
+}
+
+/// Appends the normalized `name` to `normalized`
+fn normalize_into(name: &str, normalized: &mut String) {
+    let start = normalized.len();
+    // Only names with other letters than ASCII ones need folding
+    let folded;
+    let name = if name.is_ascii() {
+        name
+    } else {
+        folded = to_base_letters(name);
+        &folded
+    };
+    for c in name.chars() {
+        let c = match c.to_ascii_uppercase() {
+            '-' | '\'' | '’' | '.' => ' ',
+            c => c,
+        };
+        if c == ' ' && (normalized.len() == start || normalized.ends_with(' ')) {
+            continue;
+        }
+        normalized.push(c);
+    }
+    if normalized.len() > start && normalized.ends_with(' ') {
+        normalized.pop();
+    }
+}
+
+/// Best matches first: exact names, then existing municipalities, then
+/// shorter and alphabetically sorted names
+fn rank(query: &str, a: (&str, &Municipality), b: (&str, &Municipality)) -> Ordering {
+    (a.0 != query)
+        .cmp(&(b.0 != query))
+        .then(b.1.active.cmp(&a.1.active))
+        .then(a.0.len().cmp(&b.0.len()))
+        .then(a.0.cmp(b.0))
+        .then(a.1.province.cmp(&b.1.province))
+}
+
+impl Belfiore {
+    /// Search municipalities whose name, in any of their languages, starts with `prefix`,
+    /// ignoring case, accents and apostrophes. At most `limit` results are returned, the
+    /// best ones first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codice_fiscale::*;
+    /// use codice_fiscale::search::SearchFilter;
+    ///
+    /// let found = BELFIORE_STORE.search_prefix("rovi", &SearchFilter::default(), 10);
+    /// assert_eq!(found[0].name, "ROVIGO");
+    /// ```
+    pub fn search_prefix(
+        &self,
+        prefix: &str,
+        filter: &SearchFilter,
+        limit: usize,
+    ) -> Vec<&Municipality> {
+        let prefix = normalize(prefix);
+        let index = &self.index;
+        let start = index
+            .entries
+            .partition_point(|entry| index.key(entry) < prefix.as_str());
+        let mut found: Vec<(&str, usize)> = index.entries[start..]
+            .iter()
+            .map(|entry| (index.key(entry), entry.2))
+            .take_while(|(key, _)| key.starts_with(&prefix))
+            .filter(|(_, i)| filter.matches(&self.store[*i]))
+            .collect();
+        found.sort_by(|a, b| rank(&prefix, (a.0, &self.store[a.1]), (b.0, &self.store[b.1])));
+        // A municipality matching with several names is only returned for the best one
+        let mut seen = HashSet::new();
+        found.retain(|(_, i)| seen.insert(*i));
+        found
+            .into_iter()
+            .take(limit)
+            .map(|(_, i)| &self.store[i])
+            .collect()
+    }
+
+    /// Typo-tolerant search: returns municipalities whose name in any of their languages
+    /// (or a prefix of it,
+    /// so that it works while the user is still typing) is within `max_distance`
+    /// edits from `query`. At most `limit` results are returned, the closest ones first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codice_fiscale::*;
+    /// use codice_fiscale::search::SearchFilter;
+    ///
+    /// let found = BELFIORE_STORE.search_fuzzy("maniagp", 1, &SearchFilter::default(), 10);
+    /// assert_eq!(found[0].municipality.name, "MANIAGO");
+    /// assert_eq!(found[0].distance, 1);
+    /// ```
+    pub fn search_fuzzy(
+        &self,
+        query: &str,
+        max_distance: usize,
+        filter: &SearchFilter,
+        limit: usize,
+    ) -> Vec<SearchMatch<'_>> {
+        let normalized_query = normalize(query);
+        let query: Vec<char> = normalized_query.chars().collect();
+        let index = &self.index;
+        let entries = &index.entries;
+        let mut found = Vec::new();
+
+        // rows[d] is the edit distance row for the first d chars of the current
+        // name, best[d] the lowest distance between the query and a prefix of
+        // length up to d. Both are shared with the next name for the common prefix.
+        let mut rows: Vec<Vec<usize>> = vec![(0..=query.len()).collect()];
+        let mut best: Vec<usize> = vec![query.len()];
+        let mut prev_key: Vec<char> = Vec::new();
+        let mut i = 0;
+        while i < entries.len() {
+            let key: Vec<char> = index.key(&entries[i]).chars().collect();
+            let common = key
+                .iter()
+                .zip(prev_key.iter())
+                .take_while(|(a, b)| a == b)
+                .count()
+                .min(rows.len() - 1);
+            rows.truncate(common + 1);
+            best.truncate(common + 1);
+
+            let mut pruned_at = None;
+            for (depth, c) in key.iter().enumerate().skip(common) {
+                let prev = &rows[depth];
+                let mut row = Vec::with_capacity(query.len() + 1);
+                row.push(prev[0] + 1);
+                for (j, q) in query.iter().enumerate() {
+                    let substitution = prev[j] + if q == c { 0 } else { 1 };
+                    row.push(substitution.min(prev[j + 1] + 1).min(row[j] + 1));
+                }
+                let row_min = *row.iter().min().unwrap();
+                best.push(best[depth].min(row[query.len()]));
+                rows.push(row);
+                if row_min > max_distance {
+                    pruned_at = Some(depth + 1);
+                    break;
+                }
+            }
+
+            // Once pruned, no name sharing this prefix can get any closer, so
+            // they all get the same distance without walking them
+            let distance = best[best.len() - 1];
+            let end = match pruned_at {
+                Some(depth) => {
+                    let dead: String = key[..depth].iter().collect();
+                    i + entries[i..]
+                        .iter()
+                        .take_while(|entry| index.key(entry).starts_with(&dead))
+                        .count()
+                }
+                None => i + 1,
+            };
+            if distance <= max_distance {
+                for entry in &entries[i..end] {
+                    let municipality = &self.store[entry.2];
+                    if filter.matches(municipality) {
+                        found.push((index.key(entry), municipality, distance, entry.2));
+                    }
+                }
+            }
+            i = end;
+            prev_key = key;
+        }
+
+        found.sort_by(|a, b| {
+            a.2.cmp(&b.2)
+                .then_with(|| rank(&normalized_query, (a.0, a.1), (b.0, b.1)))
+        });
+        let mut seen = HashSet::new();
+        found.retain(|(_, _, _, i)| seen.insert(*i));
+        found
+            .into_iter()
+            .take(limit)
+            .map(|(_, municipality, distance, _)| SearchMatch {
+                municipality,
+                distance,
+            })
+            .collect()
+    }
+}
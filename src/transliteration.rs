@@ -0,0 +1,236 @@
+use crate::utils::to_base_letters;
+use crate::{Error, PersonData};
+
+/// How names written in other scripts are turned into Latin letters before being encoded
+///
+/// # Examples
+///
+/// ```
+/// use codice_fiscale::*;
+/// use codice_fiscale::transliteration::Transliteration;
+///
+/// let person = PersonData {
+///     name: "Иван".to_string(),
+///     surname: "Иванов".to_string(),
+///     birthdate: "1980-05-04".to_string(),
+///     gender: Gender::M,
+///     place_of_birth: BELFIORE_STORE.lookup_belfiore("Z154").unwrap().clone(),
+/// };
+/// let latin = Transliteration::Builtin.apply(&person).unwrap();
+/// assert_eq!(latin.surname, "IVANOV");
+///
+/// // The passport spells the name differently: use it instead
+/// let mrz = Transliteration::Mrz("IVANOFF<<IWAN".to_string()).apply(&person).unwrap();
+/// assert_eq!(mrz.surname, "IVANOFF");
+/// assert_eq!(mrz.name, "IWAN");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Transliteration {
+    /// Built-in tables: ICAO Doc 9303 for Cyrillic, ELOT 743 for Greek. Other scripts
+    /// (ie Arabic or Chinese) are not supported: use the passport spelling instead.
+    Builtin,
+    /// The names as spelled in the machine readable zone of the passport, used instead
+    /// of the given ones: either the name field (`IVANOV<<IVAN<PETROVICH`) or the whole
+    /// first line (`P<RUSIVANOV<<IVAN<PETROVICH<<<<<<<<<<<<<<<<<<`)
+    Mrz(String),
+}
+
+impl Transliteration {
+    /// Returns a copy of `person` whose name and surname only use Latin letters
+    ///
+    /// # Errors
+    ///
+    /// * *invalid-name*, *invalid-surname* - letters which can't be transliterated, or a
+    ///   malformed MRZ
+    pub fn apply(&self, person: &PersonData) -> Result<PersonData, Error> {
+        let (surname, name) = match self {
+            Transliteration::Builtin => {
+                (transliterate(&person.surname), transliterate(&person.name))
+            }
+            Transliteration::Mrz(mrz) => parse_mrz(mrz)?,
+        };
+        if !is_latin(&surname) {
+            return Err(Error::InvalidSurname);
+        }
+        if !is_latin(&name) {
+            return Err(Error::InvalidName);
+        }
+        Ok(PersonData {
+            name,
+            surname,
+            ..person.clone()
+        })
+    }
+}
+
+/// True if every letter is an (unaccented) Latin one, once diacritics are folded
+pub fn is_latin(text: &str) -> bool {
+    to_base_letters(text)
+        .chars()
+        .all(|c| !c.is_alphabetic() || c.is_ascii_alphabetic())
+}
+
+/// Uppercases and transliterates Cyrillic and Greek letters. Latin letters, including
+/// the ones with diacritics, and other scripts are left as they are.
+///
+/// # Examples
+///
+/// ```
+/// use codice_fiscale::transliteration::transliterate;
+///
+/// assert_eq!(transliterate("Щербаков"), "SHCHERBAKOV");
+/// assert_eq!(transliterate("Παπαδόπουλος"), "PAPADOPOULOS");
+/// assert_eq!(transliterate("Nicolò"), "NICOLÒ");
+/// ```
+pub fn transliterate(text: &str) -> String {
+    let chars: Vec<char> = text.chars().flat_map(char::to_uppercase).collect();
+    let mut latin = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let next = chars.get(i + 1).copied();
+        if let Some((digraph, skip)) = greek_digraph(chars[i], next, chars.get(i + 2).copied()) {
+            latin.push_str(digraph);
+            i += skip;
+            continue;
+        }
+        match cyrillic(chars[i]).or_else(|| greek(chars[i])) {
+            Some(t) => latin.push_str(t),
+            None => latin.push(chars[i]),
+        }
+        i += 1;
+    }
+    latin
+}
+
+/// Surname and name from the name field of a passport MRZ
+fn parse_mrz(mrz: &str) -> Result<(String, String), Error> {
+    let mrz = mrz.trim().to_uppercase();
+    // The MRZ alphabet is A-Z, 0-9 and <
+    if !mrz.is_ascii() {
+        return Err(Error::InvalidSurname);
+    }
+    // Whole first line of a passport: document code, filler, issuing state
+    let field = if mrz.len() == 44 && mrz.starts_with('P') {
+        &mrz[5..]
+    } else {
+        &mrz[..]
+    };
+    let mut parts = field.splitn(2, "<<");
+    let surname = parts
+        .next()
+        .unwrap_or("")
+        .replace('<', " ")
+        .trim()
+        .to_string();
+    let name = parts
+        .next()
+        .unwrap_or("")
+        .replace('<', " ")
+        .trim()
+        .to_string();
+    if surname.is_empty() {
+        return Err(Error::InvalidSurname);
+    }
+    if name.is_empty() {
+        return Err(Error::InvalidName);
+    }
+    Ok((surname, name))
+}
+
+// ICAO Doc 9303, part 3, transliteration of Cyrillic characters
+fn cyrillic(c: char) -> Option<&'static str> {
+    let t = match c {
+        'А' => "A",
+        'Б' => "B",
+        'В' => "V",
+        'Г' | 'Ѓ' | 'Ґ' => "G",
+        'Д' | 'Ђ' => "D",
+        'Е' | 'Ё' | 'Э' => "E",
+        'Є' => "IE",
+        'Ж' => "ZH",
+        'З' => "Z",
+        'Ѕ' | 'Џ' => "DZ",
+        'И' | 'Й' | 'І' | 'Ї' => "I",
+        'Ј' => "J",
+        'К' | 'Ќ' => "K",
+        'Л' => "L",
+        'Љ' => "LJ",
+        'М' => "M",
+        'Н' => "N",
+        'Њ' => "NJ",
+        'О' => "O",
+        'П' => "P",
+        'Р' => "R",
+        'С' => "S",
+        'Т' => "T",
+        'Ћ' => "C",
+        'У' | 'Ў' => "U",
+        'Ф' => "F",
+        'Х' => "KH",
+        'Ц' => "TS",
+        'Ч' => "CH",
+        'Ш' => "SH",
+        'Щ' => "SHCH",
+        'Ъ' => "IE",
+        'Ы' => "Y",
+        'Ь' => "",
+        'Ю' => "IU",
+        'Я' => "IA",
+        _ => return None,
+    };
+    Some(t)
+}
+
+// ELOT 743, as used on Greek passports
+fn greek(c: char) -> Option<&'static str> {
+    let t = match c {
+        'Α' | 'Ά' => "A",
+        'Β' => "V",
+        'Γ' => "G",
+        'Δ' => "D",
+        'Ε' | 'Έ' => "E",
+        'Ζ' => "Z",
+        'Η' | 'Ή' | 'Ι' | 'Ί' | 'Ϊ' | 'ΐ' => "I",
+        'Θ' => "TH",
+        'Κ' => "K",
+        'Λ' => "L",
+        'Μ' => "M",
+        'Ν' => "N",
+        'Ξ' => "X",
+        'Ο' | 'Ό' | 'Ω' | 'Ώ' => "O",
+        'Π' => "P",
+        'Ρ' => "R",
+        'Σ' | 'ς' => "S",
+        'Τ' => "T",
+        'Υ' | 'Ύ' | 'Ϋ' | 'ΰ' => "Y",
+        'Φ' => "F",
+        'Χ' => "CH",
+        'Ψ' => "PS",
+        _ => return None,
+    };
+    Some(t)
+}
+
+/// ΟΥ is OU; ΑΥ and ΕΥ are AV and EV, or AF and EF before voiceless consonants and at the
+/// end of words; ΓΓ, ΓΞ and ΓΧ are NG, NX and NCH. Returns the spelling and the number
+/// of chars it takes.
+fn greek_digraph(
+    c: char,
+    next: Option<char>,
+    after: Option<char>,
+) -> Option<(&'static str, usize)> {
+    let voiceless =
+        |x: Option<char>| x.map_or(true, |x| "ΘΚΞΠΣΤΦΧΨς".contains(x) || !x.is_alphabetic());
+    let t = match (c, next?) {
+        ('Ο', 'Υ') | ('Ο', 'Ύ') => "OU",
+        ('Α', 'Υ') | ('Α', 'Ύ') if voiceless(after) => "AF",
+        ('Α', 'Υ') | ('Α', 'Ύ') => "AV",
+        ('Ε', 'Υ') | ('Ε', 'Ύ') if voiceless(after) => "EF",
+        ('Ε', 'Υ') | ('Ε', 'Ύ') => "EV",
+        ('Γ', 'Γ') => "NG",
+        ('Γ', 'Ξ') => "NX",
+        ('Γ', 'Χ') => "NCH",
+        _ => return None,
+    };
+    Some((t, 2))
+}
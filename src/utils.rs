@@ -1,3 +1,7 @@
+use crate::names::letters;
+use crate::transliteration::transliterate;
+use std::fmt;
+
 static CONSONANTS: &str = "BCDFGHJKLMNPQRSTVWXYZ";
 static VOWELS: &str = "AEIOU";
 
@@ -9,62 +13,164 @@ fn is_consonant(c: &char) -> bool {
     CONSONANTS.contains(*c)
 }
 
-fn extract_consonants(name: &str) -> String {
-    name.to_uppercase()
-        .chars()
-        .filter(|x| is_vowel(x))
-        .rev()
-        .collect()
-}
-
-pub fn calc_name_component(name: &str) -> String {
-    let name = name.to_uppercase();
-    let consonants = name.chars().filter(is_consonant);
-    let mut consonants: String = if consonants.count() <= 3 {
-        name.chars().filter(is_consonant).take(3).collect()
-    } else {
-        name.chars()
-            .filter(is_consonant)
-            .enumerate()
-            .filter(|x| x.0 != 1)
-            .map(|x| x.1)
-            .take(3)
-            .collect()
+/// The base letters of an uppercase Latin letter with diacritics, as the Agenzia delle
+/// Entrate transcribes them: Ò is O, Ñ is N, Æ is AE. `None` for any other char.
+pub(crate) fn fold_diacritic(c: char) -> Option<&'static str> {
+    let folded = match c {
+        'À' | 'Á' | 'Â' | 'Ã' | 'Ä' | 'Å' | 'Ā' | 'Ă' | 'Ą' => "A",
+        'Æ' => "AE",
+        'Ç' | 'Ć' | 'Ĉ' | 'Ċ' | 'Č' => "C",
+        'Ð' | 'Ď' | 'Đ' => "D",
+        'È' | 'É' | 'Ê' | 'Ë' | 'Ē' | 'Ĕ' | 'Ė' | 'Ę' | 'Ě' => "E",
+        'Ĝ' | 'Ğ' | 'Ġ' | 'Ģ' => "G",
+        'Ĥ' | 'Ħ' => "H",
+        'Ì' | 'Í' | 'Î' | 'Ï' | 'Ĩ' | 'Ī' | 'Ĭ' | 'Į' | 'İ' => "I",
+        'Ĵ' => "J",
+        'Ķ' => "K",
+        'Ĺ' | 'Ļ' | 'Ľ' | 'Ŀ' | 'Ł' => "L",
+        'Ñ' | 'Ń' | 'Ņ' | 'Ň' => "N",
+        'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ö' | 'Ø' | 'Ō' | 'Ŏ' | 'Ő' => "O",
+        'Œ' => "OE",
+        'Ŕ' | 'Ŗ' | 'Ř' => "R",
+        'Ś' | 'Ŝ' | 'Ş' | 'Š' | 'Ș' => "S",
+        'Ţ' | 'Ť' | 'Ŧ' | 'Ț' => "T",
+        'Ù' | 'Ú' | 'Û' | 'Ü' | 'Ũ' | 'Ū' | 'Ŭ' | 'Ů' | 'Ű' | 'Ų' => "U",
+        'Ŵ' => "W",
+        'Ý' | 'Ÿ' | 'Ŷ' => "Y",
+        'Ź' | 'Ż' | 'Ž' => "Z",
+        _ => return None,
     };
+    Some(folded)
+}
 
-    if consonants.len() < 3 {
-        consonants += &name
-            .chars()
-            .filter(is_vowel)
-            .take(3 - consonants.len())
-            .collect::<String>();
+/// Uppercases, transliterates Cyrillic and Greek letters and replaces Latin letters with
+/// diacritics by their base letters, both when precomposed (ò) and when followed by
+/// combining marks (o + U+0300)
+pub(crate) fn to_base_letters(name: &str) -> String {
+    let mut folded = String::with_capacity(name.len());
+    for c in transliterate(name).chars() {
+        match fold_diacritic(c) {
+            Some(base) => folded.push_str(base),
+            None if ('\u{300}'..='\u{36f}').contains(&c) => (),
+            None => folded.push(c),
+        }
     }
-    if consonants.len() < 3 {
-        consonants += &['X']
-            .iter()
-            .cycle()
-            .take(3 - consonants.len())
-            .collect::<String>();
+    folded
+}
+
+/// The steps of the surname and name algorithms
+///
+/// # Examples
+///
+/// ```
+/// use codice_fiscale::names::trace_name_component;
+///
+/// let trace = trace_name_component("Gianfranco");
+/// assert_eq!(trace.consonants, "GNFRNC");
+/// assert_eq!(trace.dropped_consonant, Some('N'));
+/// assert_eq!(trace.code, "GFR");
+/// println!("{}", trace);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComponentTrace {
+    /// The letters the code is computed from, see `names::letters()`
+    pub letters: String,
+    /// All the consonants, in order
+    pub consonants: String,
+    /// The second consonant, which names with more than three consonants skip
+    pub dropped_consonant: Option<char>,
+    /// The consonants taken, at most three
+    pub used_consonants: String,
+    /// All the vowels, in order
+    pub vowels: String,
+    /// The vowels taken when there are less than three consonants
+    pub appended_vowels: String,
+    /// How many X are appended when there are less than three letters
+    pub padding: usize,
+    /// The resulting three letters
+    pub code: String,
+}
+
+impl fmt::Display for ComponentTrace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Letters: {}", self.letters)?;
+        writeln!(f, "Consonants: {}", self.consonants)?;
+        if let Some(c) = self.dropped_consonant {
+            writeln!(
+                f,
+                "More than three consonants, the second one ({}) is skipped",
+                c
+            )?;
+        }
+        writeln!(f, "Consonants taken: {}", self.used_consonants)?;
+        if !self.appended_vowels.is_empty() {
+            writeln!(
+                f,
+                "Less than three consonants, vowels added: {}",
+                self.appended_vowels
+            )?;
+        }
+        if self.padding > 0 {
+            writeln!(f, "Less than three letters, X added: {}", self.padding)?;
+        }
+        write!(f, "Code: {}", self.code)
     }
-    dbg!(consonants)
 }
 
-pub fn calc_surname_component(name: &str) -> String {
-    let part_consonants: String = name
-        .to_uppercase()
+fn trace_component(name: &str, skip_second: bool) -> ComponentTrace {
+    let letters = letters(name);
+    let consonants: String = letters.chars().filter(is_consonant).collect();
+    let vowels: String = letters.chars().filter(is_vowel).collect();
+    let dropped_consonant = if skip_second && consonants.len() > 3 {
+        consonants.chars().nth(1)
+    } else {
+        None
+    };
+    let used_consonants: String = consonants
         .chars()
-        .filter(|x| is_consonant(x))
+        .enumerate()
+        .filter(|&(i, _)| dropped_consonant.is_none() || i != 1)
+        .map(|(_, c)| c)
+        .take(3)
         .collect();
-    let mut part_vowels = extract_consonants(name);
-    let mut cf_part = String::new();
-    cf_part.push_str(part_consonants.chars().take(3).collect::<String>().as_ref());
-    // Push vowels if needed (and there are)
-    while cf_part.len() < 3 && !part_vowels.is_empty() {
-        cf_part.push(part_vowels.pop().unwrap());
-    }
-    // Push Xs for missing chars
-    while cf_part.len() < 3 {
-        cf_part.push('X');
+    let appended_vowels: String = vowels.chars().take(3 - used_consonants.len()).collect();
+    let padding = 3 - used_consonants.len() - appended_vowels.len();
+    let code = format!(
+        "{}{}{}",
+        used_consonants,
+        appended_vowels,
+        "X".repeat(padding)
+    );
+    ComponentTrace {
+        letters,
+        consonants,
+        dropped_consonant,
+        used_consonants,
+        vowels,
+        appended_vowels,
+        padding,
+        code,
     }
-    cf_part
+}
+
+/// Same as `calc_name_component()`, returning the intermediate steps
+pub fn trace_name_component(name: &str) -> ComponentTrace {
+    trace_component(name, true)
+}
+
+/// Same as `calc_surname_component()`, returning the intermediate steps
+pub fn trace_surname_component(surname: &str) -> ComponentTrace {
+    trace_component(surname, false)
+}
+
+/// The three letters of the name: the first, third and fourth consonants if there are more
+/// than three, otherwise the consonants followed by the vowels and by X
+pub fn calc_name_component(name: &str) -> String {
+    trace_name_component(name).code
+}
+
+/// The three letters of the surname: the first three consonants, followed by the vowels
+/// and by X if there aren't enough
+pub fn calc_surname_component(surname: &str) -> String {
+    trace_surname_component(surname).code
 }
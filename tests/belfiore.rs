@@ -0,0 +1,94 @@
+#![cfg(test)]
+#![cfg(feature = "embedded-data")]
+extern crate codice_fiscale;
+use codice_fiscale::*;
+
+#[test]
+fn t_iter() {
+    assert_eq!(
+        BELFIORE_STORE.iter().count(),
+        BELFIORE_STORE.italian().count() + BELFIORE_STORE.foreign().count()
+    );
+}
+
+#[test]
+fn t_by_province() {
+    let udine: Vec<&belfiore::Municipality> = BELFIORE_STORE.by_province("ud").collect();
+    assert!(!udine.is_empty());
+    assert!(udine.iter().all(|m| m.province == "UD"));
+    assert!(udine.iter().any(|m| m.name == "UDINE"));
+}
+
+#[test]
+fn t_by_status() {
+    assert!(BELFIORE_STORE.by_status(true).all(|m| m.active));
+    assert!(BELFIORE_STORE
+        .by_status(false)
+        .any(|m| m.name == "ABANO BAGNI"));
+}
+
+#[test]
+fn t_foreign() {
+    assert!(BELFIORE_STORE
+        .foreign()
+        .all(|m| m.belfiore_code.starts_with('Z')));
+    assert!(BELFIORE_STORE.foreign().any(|m| m.name == "FRANCIA"));
+    assert!(BELFIORE_STORE.italian().all(|m| m.province != "EE"));
+}
+
+#[test]
+fn t_istat() {
+    assert_eq!(
+        BELFIORE_STORE.lookup_istat("093025").unwrap().belfiore_code,
+        "E889"
+    );
+    assert_eq!(BELFIORE_STORE.belfiore_to_istat("e889"), Some("093025"));
+    assert_eq!(BELFIORE_STORE.istat_to_belfiore("93025"), Some("E889"));
+}
+
+#[test]
+fn t_by_code_range() {
+    let codes: Vec<&str> = BELFIORE_STORE
+        .by_code_range("E888"..="E890")
+        .map(|m| &*m.belfiore_code)
+        .collect();
+    assert!(codes.contains(&"E889"));
+    assert!(codes.iter().all(|c| ("E888"..="E890").contains(c)));
+    assert_eq!(
+        BELFIORE_STORE.by_code_range("Z".."[").count(),
+        BELFIORE_STORE.foreign().count()
+    );
+    let lowercase: Vec<&str> = BELFIORE_STORE
+        .by_code_range("e888"..="e890")
+        .map(|m| &*m.belfiore_code)
+        .collect();
+    assert_eq!(lowercase, codes);
+}
+
+#[test]
+fn t_alt_names() {
+    use codice_fiscale::belfiore::Language;
+
+    let bolzano = BELFIORE_STORE.get_info("bozen").unwrap();
+    assert_eq!(bolzano.belfiore_code, "A952");
+    assert_eq!(bolzano.name_in(Language::German), "BOZEN");
+    assert_eq!(BELFIORE_STORE.get_info("Aoste").unwrap().name, "AOSTA");
+    assert_eq!(
+        BELFIORE_STORE
+            .get_info("Trst")
+            .unwrap()
+            .name_in(Language::Slovenian),
+        "TRST"
+    );
+    let ortisei = BELFIORE_STORE.get_info("Urtijëi").unwrap();
+    assert_eq!(
+        ortisei.names().collect::<Vec<&str>>(),
+        vec!["ORTISEI", "ST. ULRICH IN GRÖDEN", "URTIJËI"]
+    );
+    // Italian names take precedence and suppressed entries get no alternative name
+    assert!(BELFIORE_STORE
+        .get_info("Maniago")
+        .unwrap()
+        .alt_names
+        .is_empty());
+}
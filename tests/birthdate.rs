@@ -0,0 +1,119 @@
+#![cfg(test)]
+#![cfg(feature = "embedded-data")]
+extern crate codice_fiscale;
+use codice_fiscale::birthdate::*;
+use codice_fiscale::*;
+use time::macros::date;
+
+fn parser() -> BirthdateParser {
+    BirthdateParser::default().today(date!(2024 - 06 - 30))
+}
+
+#[test]
+fn t_formats() {
+    for text in &[
+        "1977-11-04",
+        "04/11/1977",
+        "4/11/1977",
+        "4-11-77",
+        "04.11.77",
+        "4 11 1977",
+        "4 novembre 1977",
+        "4 Novembre, 1977",
+        "4 nov 77",
+        "04-NOV-1977",
+    ] {
+        assert_eq!(parser().parse(text), Ok(date!(1977 - 11 - 04)), "{}", text);
+    }
+    assert_eq!(parser().parse("1° gennaio 2000"), Ok(date!(2000 - 01 - 01)));
+    assert_eq!(parser().parse("29 feb 2000"), Ok(date!(2000 - 02 - 29)));
+}
+
+#[test]
+fn t_invalid() {
+    for text in &[
+        "",
+        "1977-11-31",
+        "31/04/1977",
+        "29 febbraio 1977",
+        "4 novembr 1977",
+        "4 no 1977",
+        "4 brumaio 1977",
+        "11/04/1977/1",
+        "4/11/977",
+        "04111977",
+        "4:11:1977",
+    ] {
+        assert_eq!(
+            parser().parse(text),
+            Err(Error::InvalidBirthdate(Some(text.to_string()))),
+            "{}",
+            text
+        );
+    }
+}
+
+#[test]
+fn t_two_digit_years() {
+    // 2077 is in the future, 1924 and 2024 both in the past
+    assert_eq!(parser().parse("4/11/77"), Ok(date!(1977 - 11 - 04)));
+    assert_eq!(
+        parser().parse("30/06/24"),
+        Err(Error::AmbiguousBirthdate(vec![
+            "1924-06-30".to_string(),
+            "2024-06-30".to_string()
+        ]))
+    );
+    // Tomorrow: only 1924
+    assert_eq!(parser().parse("1 lug 24"), Ok(date!(1924 - 07 - 01)));
+    // 1900 and 2100 are not leap years, 2000 was
+    assert_eq!(parser().parse("29/02/00"), Ok(date!(2000 - 02 - 29)));
+}
+
+#[test]
+fn t_formats_restricted() {
+    let parser = BirthdateParser::new(&[DateFormat::Dmy]);
+    assert!(parser.parse("04/11/1977").is_ok());
+    assert!(parser.parse("1977-11-04").is_err());
+    assert!(parser.parse("4 novembre 1977").is_err());
+}
+
+#[test]
+fn t_new_with_italian_date() {
+    let person = PersonData {
+        name: "Michele".to_string(),
+        surname: "Beltrame".to_string(),
+        birthdate: "4 novembre 1977".to_string(),
+        gender: Gender::M,
+        place_of_birth: BELFIORE_STORE.get_info("Maniago").unwrap().clone(),
+    };
+    let cf = CodiceFiscale::new(&person).unwrap();
+    assert_eq!(cf.get_codice(), "BLTMHL77S04E889G");
+    assert_eq!(cf.get_person_data().birthdate, "1977-11-04");
+
+    // Two-digit years depend on today's date: new() doesn't accept them
+    let person = PersonData {
+        birthdate: "4/11/77".to_string(),
+        ..person
+    };
+    assert_eq!(
+        CodiceFiscale::new(&person).err(),
+        Some(Error::InvalidBirthdate(None))
+    );
+
+    let err = PersonData::builder()
+        .name("Michele")
+        .surname("Beltrame")
+        .birthdate_str("4/11/10")
+        .gender(Gender::M)
+        .born_in("Maniago", None)
+        .build()
+        .err();
+    assert_eq!(
+        err,
+        Some(Error::AmbiguousBirthdate(vec![
+            "1910-11-04".to_string(),
+            "2010-11-04".to_string()
+        ]))
+    );
+}
@@ -0,0 +1,160 @@
+#![cfg(test)]
+#![cfg(feature = "embedded-data")]
+extern crate codice_fiscale;
+use codice_fiscale::transliteration::Transliteration;
+use codice_fiscale::*;
+use time::macros::date;
+
+fn michele() -> builder::PersonDataBuilder {
+    PersonData::builder()
+        .name("Michele")
+        .surname("Beltrame")
+        .birthdate(date!(1977 - 11 - 04))
+        .gender(Gender::M)
+        .born_in("Maniago", None)
+}
+
+#[test]
+fn t_build() {
+    let person = michele().build().unwrap();
+    assert_eq!(person.birthdate, "1977-11-04");
+    assert_eq!(person.place_of_birth.belfiore_code, "E889");
+    assert_eq!(
+        CodiceFiscale::new(&person).unwrap().get_codice(),
+        "BLTMHL77S04E889G"
+    );
+}
+
+#[test]
+fn t_birthdate_formats() {
+    for text in &["1977-11-04", "04/11/1977", "04-11-1977", "04.11.1977"] {
+        let cf = michele().birthdate_str(text).codice_fiscale().unwrap();
+        assert_eq!(cf.get_codice(), "BLTMHL77S04E889G", "{}", text);
+    }
+    for text in &[
+        "1977-13-04",
+        "31/04/1977",
+        "04111977",
+        "1899-12-31",
+        "2999-01-01",
+    ] {
+        assert_eq!(
+            michele().birthdate_str(text).build().err(),
+            Some(Error::InvalidBirthdate(Some(text.to_string())))
+        );
+    }
+}
+
+#[test]
+fn t_birthplace() {
+    let cf = michele().born_in_belfiore("e889").codice_fiscale().unwrap();
+    assert_eq!(cf.get_codice(), "BLTMHL77S04E889G");
+    assert_eq!(
+        michele().born_in_belfiore("Q000").build().err(),
+        Some(Error::InvalidBelfioreCode)
+    );
+
+    // Accents and alternative names
+    let person = michele()
+        .born_in("Città di Castello", None)
+        .build()
+        .unwrap();
+    assert_eq!(person.place_of_birth.province, "PG");
+    let person = michele().born_in("Bozen", None).build().unwrap();
+    assert_eq!(person.place_of_birth.name, "BOLZANO");
+
+    assert_eq!(
+        michele().born_in("Samone", None).build().err(),
+        Some(Error::AmbiguousPlace(vec![
+            "SAMONE (TO)".to_string(),
+            "SAMONE (TN)".to_string()
+        ]))
+    );
+    let person = michele().born_in("Samone", Some("tn")).build().unwrap();
+    assert_eq!(person.place_of_birth.belfiore_code, "H754");
+    assert_eq!(
+        michele().born_in("Samone", Some("PN")).build().err(),
+        Some(Error::UnknownPlace("Samone".to_string()))
+    );
+}
+
+#[test]
+fn t_born_abroad() {
+    for country in &["Germania", "DE", "deu", "Z112"] {
+        let cf = PersonData::builder()
+            .name("Hans")
+            .surname("Müller")
+            .birthdate_str("1985-03-12")
+            .gender(Gender::M)
+            .born_abroad(country)
+            .codice_fiscale()
+            .unwrap();
+        assert_eq!(cf.get_codice(), "MLLHNS85C12Z112O", "{}", country);
+    }
+    assert_eq!(
+        michele().born_abroad("Atlantide").build().err(),
+        Some(Error::UnknownPlace("Atlantide".to_string()))
+    );
+}
+
+#[test]
+fn t_missing_and_invalid_fields() {
+    assert_eq!(
+        PersonData::builder().name("Michele").build().err(),
+        Some(Error::MissingField("surname"))
+    );
+    assert_eq!(
+        michele().surname("").build().err(),
+        Some(Error::InvalidSurname)
+    );
+    assert_eq!(michele().name("王").build().err(), Some(Error::InvalidName));
+    let person = michele()
+        .name("伟")
+        .surname("王")
+        .transliteration(Transliteration::Mrz("WANG<<WEI".to_string()))
+        .build()
+        .unwrap();
+    assert_eq!(person.name, "WEI");
+}
+
+#[test]
+fn t_custom_store() {
+    use codice_fiscale::belfiore::{Municipality, MunicipalityStore};
+    use std::borrow::Cow;
+
+    struct OnlyManiago;
+
+    impl MunicipalityStore for OnlyManiago {
+        fn get_info(&self, name: &str) -> Option<Cow<'_, Municipality>> {
+            if name.eq_ignore_ascii_case("maniago") {
+                self.lookup_belfiore("E889")
+            } else {
+                None
+            }
+        }
+
+        fn lookup_belfiore(&self, belfiore: &str) -> Option<Cow<'_, Municipality>> {
+            BELFIORE_STORE
+                .lookup_belfiore(belfiore)
+                .filter(|m| m.belfiore_code == "E889")
+                .map(|m| Cow::Owned(m.clone()))
+        }
+    }
+
+    let cf = michele().codice_fiscale_with_store(&OnlyManiago).unwrap();
+    assert_eq!(cf.get_codice(), "BLTMHL77S04E889G");
+    assert_eq!(
+        michele()
+            .born_in("Porcia", None)
+            .build_with_store(&OnlyManiago)
+            .err(),
+        Some(Error::UnknownPlace("Porcia".to_string()))
+    );
+    assert_eq!(
+        michele()
+            .born_abroad("DE")
+            .build_with_store(&OnlyManiago)
+            .err(),
+        Some(Error::UnknownPlace("DE".to_string()))
+    );
+}
@@ -0,0 +1,68 @@
+#![cfg(all(feature = "cap", feature = "embedded-data"))]
+extern crate codice_fiscale;
+use codice_fiscale::belfiore::{Belfiore, LoadError};
+use codice_fiscale::*;
+
+const CAPS: &str =
+    "H501,00118,00199\nF205,20121,20162\nA952,39100\nE889,33085\nG886,33080\nD621,33080";
+
+fn store() -> Belfiore {
+    Belfiore::init().with_caps(CAPS.as_bytes()).unwrap()
+}
+
+#[test]
+fn t_no_embedded_caps() {
+    assert!(BELFIORE_STORE.caps("H501").is_empty());
+    assert_eq!(BELFIORE_STORE.is_cap_of("H501", "00118"), None);
+}
+
+#[test]
+fn t_caps() {
+    let store = store();
+    let milan = store.get_info("Milano").unwrap();
+    let ranges = store.caps(&milan.belfiore_code);
+    assert_eq!(ranges.len(), 1);
+    assert!(ranges[0].contains("20121"));
+    assert!(ranges[0].contains("20162"));
+    assert!(!ranges[0].contains("20163"));
+    assert!(!ranges[0].contains("2012"));
+    assert_eq!(store.caps("e889")[0].first, "33085");
+    assert_eq!(store.caps("e889")[0].last, "33085");
+}
+
+#[test]
+fn t_lookup_cap() {
+    let store = store();
+    assert_eq!(store.lookup_cap("00185")[0].name, "ROMA");
+    assert_eq!(store.lookup_cap("39100")[0].name, "BOLZANO");
+    assert_eq!(store.lookup_cap("33080").len(), 2);
+    assert!(store.lookup_cap("99999").is_empty());
+}
+
+#[test]
+fn t_is_cap_of() {
+    let store = store();
+    assert_eq!(store.is_cap_of("h501", "00118"), Some(true));
+    assert_eq!(store.is_cap_of("H501", "20121"), Some(false));
+    // Not in the loaded table
+    assert_eq!(store.is_cap_of("A001", "35031"), None);
+}
+
+#[test]
+fn t_with_caps() {
+    let store = store().with_caps("E889,33085".as_bytes()).unwrap();
+    // The table loaded before is replaced
+    assert!(store.caps("H501").is_empty());
+
+    assert_eq!(
+        Belfiore::init().with_caps("E889,3308".as_bytes()).err(),
+        Some(LoadError::InvalidLine {
+            line: 1,
+            reason: "invalid CAP range".to_string()
+        })
+    );
+    assert_eq!(
+        Belfiore::init().with_caps("".as_bytes()).err(),
+        Some(LoadError::Empty)
+    );
+}
@@ -0,0 +1,154 @@
+#![cfg(test)]
+#![cfg(feature = "embedded-data")]
+extern crate codice_fiscale;
+use codice_fiscale::country::{Continent, Place};
+use codice_fiscale::*;
+use time::macros::date;
+
+#[test]
+fn t_continent() {
+    assert_eq!(Continent::from_belfiore("Z110"), Some(Continent::Europe));
+    assert_eq!(
+        Continent::from_belfiore("z602"),
+        Some(Continent::SouthAmerica)
+    );
+    assert_eq!(Continent::from_belfiore("Z907"), Some(Continent::Africa));
+    assert_eq!(Continent::from_belfiore("E889"), None);
+}
+
+#[test]
+fn t_lookup_country() {
+    let uk = BELFIORE_STORE.lookup_country("Z114").unwrap();
+    assert_eq!(uk.name, "REGNO UNITO");
+    assert_eq!(uk.iso_alpha2.as_deref(), Some("GB"));
+    assert_eq!(uk.iso_alpha3.as_deref(), Some("GBR"));
+    assert!(BELFIORE_STORE.lookup_country("E889").is_none());
+
+    // States which no longer exist have no ISO code
+    let ussr = BELFIORE_STORE.lookup_country("Z135").unwrap();
+    assert_eq!(ussr.continent, Continent::Europe);
+    assert!(ussr.iso_alpha2.is_none());
+}
+
+#[test]
+fn t_lookup_iso() {
+    assert_eq!(
+        BELFIORE_STORE.lookup_iso("fr").unwrap().belfiore_code,
+        "Z110"
+    );
+    assert_eq!(
+        BELFIORE_STORE.lookup_iso("ARM").unwrap().belfiore_code,
+        "Z252"
+    );
+    assert!(BELFIORE_STORE.lookup_iso("XX").is_none());
+    assert!(BELFIORE_STORE.lookup_iso("FRAN").is_none());
+}
+
+#[test]
+fn t_lookup_place() {
+    assert!(matches!(
+        BELFIORE_STORE.lookup_place("E889"),
+        Some(Place::Municipality(_))
+    ));
+    let place = BELFIORE_STORE.lookup_place("Z404").unwrap();
+    assert!(matches!(place, Place::Country(_)));
+    assert_eq!(place.name(), "STATI UNITI D'AMERICA");
+    assert!(BELFIORE_STORE.lookup_place("Z999").is_none());
+}
+
+#[test]
+fn t_countries_unique() {
+    let codes: Vec<&str> = BELFIORE_STORE
+        .countries()
+        .map(|c| c.belfiore_code.as_str())
+        .collect();
+    assert!(codes.windows(2).all(|w| w[0] < w[1]));
+    assert!(BELFIORE_STORE
+        .foreign()
+        .all(|m| codes.contains(&&*m.belfiore_code)));
+}
+
+#[test]
+fn t_new_foreign_born() {
+    let country = BELFIORE_STORE.lookup_iso("DE").unwrap();
+    let cf = CodiceFiscale::new(&PersonData {
+        name: "Michele".to_string(),
+        surname: "Beltrame".to_string(),
+        birthdate: "1977-11-04".to_string(),
+        gender: Gender::M,
+        place_of_birth: country.into(),
+    })
+    .unwrap();
+    assert_eq!(&cf.get_codice()[11..15], "Z112");
+    assert!(CodiceFiscale::check(cf.get_codice()).is_ok());
+}
+
+#[test]
+fn t_succession_data() {
+    let ussr = BELFIORE_STORE.lookup_country("Z135").unwrap();
+    assert_eq!(ussr.valid_to, Some(date!(1991 - 12 - 26)));
+    assert!(ussr.successors.contains(&"Z154".to_string()));
+
+    let yugoslavia = BELFIORE_STORE.lookup_country("Z118").unwrap();
+    assert_eq!(yugoslavia.valid_from, None);
+    assert_eq!(yugoslavia.valid_to, Some(date!(2006 - 06 - 05)));
+
+    let slovenia = BELFIORE_STORE.lookup_country("Z150").unwrap();
+    assert_eq!(slovenia.valid_from, Some(date!(1991 - 06 - 25)));
+    assert_eq!(slovenia.predecessors, vec!["Z118".to_string()]);
+
+    assert!(BELFIORE_STORE.successions().count() > 0);
+}
+
+#[test]
+fn t_historical_countries() {
+    let codes = |iso: &str, birthdate| -> Vec<String> {
+        let current = BELFIORE_STORE.lookup_iso(iso).unwrap();
+        BELFIORE_STORE
+            .historical_countries(current, birthdate)
+            .iter()
+            .map(|c| c.belfiore_code.clone())
+            .collect()
+    };
+    assert_eq!(codes("UA", date!(1985 - 01 - 01)), vec!["Z135"]);
+    assert_eq!(codes("UA", date!(1995 - 01 - 01)), vec!["Z138"]);
+    assert_eq!(codes("CZ", date!(1992 - 12 - 31)), vec!["Z105"]);
+    assert_eq!(codes("CZ", date!(1993 - 01 - 01)), vec!["Z156"]);
+    // Kosovo split from Serbia, which split from Yugoslavia
+    assert_eq!(codes("RS", date!(2007 - 01 - 01)), vec!["Z158"]);
+    assert_eq!(codes("RS", date!(1999 - 01 - 01)), vec!["Z118"]);
+    assert_eq!(codes("FR", date!(1950 - 01 - 01)), vec!["Z110"]);
+    // Germany existed before absorbing the GDR, so both are possible
+    assert_eq!(codes("DE", date!(1980 - 01 - 01)), vec!["Z112", "Z111"]);
+    assert_eq!(codes("TZ", date!(1960 - 01 - 01)), vec!["Z350", "Z356"]);
+}
+
+#[test]
+fn t_present_day_countries() {
+    let gdr = BELFIORE_STORE.lookup_country("Z111").unwrap();
+    let present: Vec<&str> = BELFIORE_STORE
+        .present_day_countries(gdr)
+        .iter()
+        .map(|c| c.belfiore_code.as_str())
+        .collect();
+    assert_eq!(present, vec!["Z112"]);
+
+    let yugoslavia = BELFIORE_STORE.lookup_country("Z118").unwrap();
+    let present = BELFIORE_STORE.present_day_countries(yugoslavia);
+    assert_eq!(present.len(), 7);
+    assert!(present.iter().all(|c| c.valid_to.is_none()));
+}
+
+#[test]
+fn t_parse_birth_country() {
+    let cf = CodiceFiscale::parse("BLTMHL77S04Z118Z").unwrap();
+    let country = cf.get_birth_country().unwrap();
+    assert_eq!(country.historical.belfiore_code, "Z118");
+    assert!(country.present_day.iter().any(|c| c.name == "SLOVENIA"));
+    // Through the MunicipalityStore trait
+    let store: &dyn belfiore::MunicipalityStore = &*BELFIORE_STORE;
+    assert_eq!(cf.get_birth_country_with_store(store), Some(country));
+
+    let cf = CodiceFiscale::parse("BLTMHL77S04E889G").unwrap();
+    assert!(cf.get_birth_country().is_none());
+}
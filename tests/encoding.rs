@@ -0,0 +1,59 @@
+#![cfg(test)]
+#![cfg(feature = "embedded-data")]
+extern crate codice_fiscale;
+use codice_fiscale::*;
+
+fn codice(name: &str, surname: &str, birthdate: &str, gender: Gender, place: &str) -> String {
+    CodiceFiscale::new(&PersonData {
+        name: name.to_string(),
+        surname: surname.to_string(),
+        birthdate: birthdate.to_string(),
+        gender,
+        place_of_birth: BELFIORE_STORE.get_info(place).unwrap().clone(),
+    })
+    .unwrap()
+    .get_codice()
+    .to_string()
+}
+
+#[test]
+fn t_accented_letters() {
+    // Ñ counts as a consonant, Ò as a vowel
+    assert_eq!(
+        codice("Nicolò", "Muñoz", "1990-05-12", Gender::M, "Maniago"),
+        "MNZNCL90E12E889P"
+    );
+    // Accented vowels fill short names and surnames instead of X
+    assert_eq!(
+        codice("Zoè", "Fò", "1985-03-02", Gender::F, "Roma"),
+        "FOXZOE85C42H501U"
+    );
+    assert_eq!(
+        codice("Sofìa", "Müller", "1992-07-15", Gender::F, "Roma"),
+        "MLLSFO92L55H501N"
+    );
+    // Ç and the Scandinavian Ø
+    assert_eq!(
+        codice("Øyvind", "Çelik", "1988-02-10", Gender::M, "Maniago"),
+        "CLKYND88B10E889Q"
+    );
+}
+
+#[test]
+fn t_lowercase_and_combining_marks() {
+    // "Nicolò" decomposed as o + combining grave accent, "Šimić" in lowercase
+    assert_eq!(
+        codice("Nicolo\u{300}", "šimić", "2001-01-01", Gender::M, "Roma"),
+        "SMCNCL01A01H501D"
+    );
+}
+
+#[test]
+fn t_validity_with_accents() {
+    let cf = CodiceFiscale::parse("MNZNCL90E12E889P").unwrap();
+    assert!(cf.is_name_valid("Nicolò"));
+    assert!(cf.is_name_valid("NICOLO'"));
+    assert!(cf.is_surname_valid("Muñoz"));
+    assert!(cf.is_surname_valid("MUNOZ"));
+    assert!(!cf.is_surname_valid("Muoz"));
+}
@@ -0,0 +1,77 @@
+#![cfg(all(feature = "geo", feature = "embedded-data"))]
+extern crate codice_fiscale;
+use codice_fiscale::belfiore::{Belfiore, LoadError};
+use codice_fiscale::geo::Coordinates;
+use codice_fiscale::*;
+
+#[test]
+fn t_coordinates() {
+    let maniago = BELFIORE_STORE.coordinates("e889").unwrap();
+    assert!((maniago.latitude - 46.17).abs() < 0.05);
+    assert!((maniago.longitude - 12.71).abs() < 0.05);
+    // Suppressed, so not in the table
+    assert_eq!(BELFIORE_STORE.coordinates("A002"), None);
+}
+
+#[test]
+fn t_distance() {
+    let point = Coordinates {
+        latitude: 45.0,
+        longitude: 10.0,
+    };
+    assert_eq!(point.distance_km(&point), 0.0);
+    // One degree of latitude is about 111 km
+    let north = Coordinates {
+        latitude: 46.0,
+        longitude: 10.0,
+    };
+    assert!((point.distance_km(&north) - 111.2).abs() < 0.1);
+    assert_eq!(point.distance_km(&north), north.distance_km(&point));
+
+    // Milan to Naples, about 660 km
+    let distance = BELFIORE_STORE.distance_km("F205", "F839").unwrap();
+    assert!(distance > 640.0 && distance < 680.0);
+    assert_eq!(BELFIORE_STORE.distance_km("F205", "A002"), None);
+}
+
+#[test]
+fn t_nearest() {
+    let spilimbergo = Coordinates {
+        latitude: 46.11345,
+        longitude: 12.89241,
+    };
+    let found = BELFIORE_STORE.nearest(spilimbergo, 3);
+    assert_eq!(found.len(), 3);
+    assert_eq!(found[0].municipality.name, "SPILIMBERGO");
+    assert_eq!(found[0].municipality.belfiore_code, "I904");
+    assert!(found[0].distance_km < 0.1);
+    assert!(found
+        .windows(2)
+        .all(|w| w[0].distance_km <= w[1].distance_km));
+}
+
+#[test]
+fn t_with_coordinates() {
+    let store = Belfiore::init()
+        .with_coordinates("E889,46.1713,12.7090\nG886,45.9588,12.6161".as_bytes())
+        .unwrap();
+    // Pordenone itself is not in the replaced table
+    assert_eq!(store.coordinates("G888"), None);
+    let pordenone = Coordinates {
+        latitude: 45.9564,
+        longitude: 12.6615,
+    };
+    let found = store.nearest(pordenone, 5);
+    assert_eq!(found.len(), 2);
+    assert_eq!(found[0].municipality.name, "PORCIA");
+
+    assert_eq!(
+        Belfiore::init()
+            .with_coordinates("E889,96.0,12.7".as_bytes())
+            .err(),
+        Some(LoadError::InvalidLine {
+            line: 1,
+            reason: "invalid coordinates".to_string()
+        })
+    );
+}
@@ -0,0 +1,139 @@
+#![cfg(test)]
+#![cfg(all(feature = "given-names", feature = "embedded-data"))]
+extern crate codice_fiscale;
+use codice_fiscale::belfiore::LoadError;
+use codice_fiscale::given_names::GivenNames;
+use codice_fiscale::*;
+
+fn names(cf: &str) -> Vec<String> {
+    CodiceFiscale::parse(cf)
+        .unwrap()
+        .get_candidate_names()
+        .iter()
+        .map(|n| n.name.clone())
+        .collect()
+}
+
+#[test]
+fn t_candidate_names() {
+    let found = names("BLTMHL77S04E889G");
+    assert!(found.contains(&"MICHELE".to_string()));
+    assert!(found.contains(&"MICHAEL".to_string()));
+    assert!(!found.contains(&"MICHELA".to_string()));
+
+    // Same code, female
+    let found = names("RSSMRA70A41H501W");
+    assert!(found.contains(&"MARIA".to_string()));
+    assert!(found.iter().all(|n| n != "MARIO"));
+}
+
+#[test]
+fn t_candidates_order() {
+    let dictionary =
+        GivenNames::from_reader("Michael,M,20\nMichela,F,150\nMichele,M,300\nMichel,M".as_bytes())
+            .unwrap();
+    let found: Vec<&str> = dictionary
+        .candidates("mhl", None)
+        .iter()
+        .map(|n| &*n.name)
+        .collect();
+    assert_eq!(found, ["MICHELE", "MICHELA", "MICHAEL", "MICHEL"]);
+    let found = dictionary.candidates("MHL", Some(Gender::M));
+    assert_eq!(found[0].frequency, Some(300));
+    assert_eq!(found[2].frequency, None);
+
+    // Without frequencies, alphabetical
+    let found = GIVEN_NAMES.candidates("mhl", None);
+    assert!(found.windows(2).all(|w| w[0].name <= w[1].name));
+    assert!(found.iter().any(|n| n.gender == Gender::F));
+    assert!(GIVEN_NAMES.candidates("QQQ", None).is_empty());
+}
+
+#[test]
+fn t_lookup() {
+    let andrea = GIVEN_NAMES.lookup("Andrea");
+    assert_eq!(andrea.len(), 2);
+    assert_eq!(andrea[0].gender, Gender::M);
+    assert_eq!(GIVEN_NAMES.lookup("Nicolò")[0].name, "NICOLO");
+}
+
+#[test]
+fn t_own_dictionary() {
+    let dictionary =
+        GivenNames::from_reader("Michele,M\nMichael,M\nGian Luca,M".as_bytes()).unwrap();
+    let cf = CodiceFiscale::parse("BLTMHL77S04E889G").unwrap();
+    let found = cf.get_candidate_names_with_dictionary(&dictionary);
+    assert_eq!(found.len(), 2);
+    assert_eq!(found[0].name, "MICHAEL");
+    assert_eq!(dictionary.candidates("GLC", None)[0].name, "GIAN LUCA");
+
+    assert_eq!(
+        GivenNames::from_reader("MICHELE,X".as_bytes()).err(),
+        Some(LoadError::InvalidLine {
+            line: 1,
+            reason: "gender must be M or F".to_string()
+        })
+    );
+    assert_eq!(
+        GivenNames::from_reader("MICHELE,M,many".as_bytes()).err(),
+        Some(LoadError::InvalidLine {
+            line: 1,
+            reason: "invalid frequency".to_string()
+        })
+    );
+    assert_eq!(
+        GivenNames::from_reader("".as_bytes()).err(),
+        Some(LoadError::Empty)
+    );
+}
+
+#[test]
+fn t_gender_warning() {
+    use codice_fiscale::given_names::Warning;
+
+    assert_eq!(
+        GIVEN_NAMES.gender_warning("giuseppe", Gender::F),
+        Some(Warning::UnusualGender {
+            name: "GIUSEPPE".to_string(),
+            gender: Gender::F
+        })
+    );
+    assert!(GIVEN_NAMES.gender_warning("Giuseppe", Gender::M).is_none());
+    // Multiple names are judged by the first one, unknown names are not reported
+    assert!(GIVEN_NAMES
+        .gender_warning("Maria Giuseppe", Gender::M)
+        .is_some());
+    assert!(GIVEN_NAMES.gender_warning("Zbigniew", Gender::F).is_none());
+}
+
+#[test]
+fn t_new_with_warnings() {
+    let place_of_birth = BELFIORE_STORE.get_info("Maniago").unwrap().clone();
+    let mut person = PersonData {
+        name: "Michele".to_string(),
+        surname: "Beltrame".to_string(),
+        birthdate: "1977-11-04".to_string(),
+        gender: Gender::M,
+        place_of_birth,
+    };
+    let (cf, warnings) = CodiceFiscale::new_with_warnings(&person).unwrap();
+    assert_eq!(cf.get_codice(), "BLTMHL77S04E889G");
+    assert!(warnings.is_empty());
+
+    // Warnings don't prevent the code from being computed
+    person.gender = Gender::F;
+    let (cf, warnings) = CodiceFiscale::new_with_warnings(&person).unwrap();
+    assert_eq!(
+        cf.get_codice(),
+        CodiceFiscale::new(&person).unwrap().get_codice()
+    );
+    assert_eq!(warnings.len(), 1);
+}
+
+#[test]
+fn t_verify_name() {
+    let cf = CodiceFiscale::parse("BLTMHL77S04E889G").unwrap();
+    assert_eq!(cf.verify_name("Michele"), Ok(vec![]));
+    assert_eq!(cf.verify_name("Michela").unwrap().len(), 1);
+    assert_eq!(cf.verify_name("Mario"), Err(Error::InvalidName));
+}
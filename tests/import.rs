@@ -0,0 +1,106 @@
+#![cfg(test)]
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+const CURRENT: &str = "E889,PN,MANIAGO,1
+G886,PN,PORCIA,1
+M999,PN,VECCHIO NOME,1
+A001,PD,ABANO BAGNI,0
+Z100,EE,ALBANIA,0";
+
+const ISTAT: &str = "Codice Comune formato alfanumerico;Denominazione in italiano;Sigla automobilistica;Codice Catastale del comune
+093025;Maniago;PN;E889
+021031;Fiè allo Sciliar;BZ;D571
+099999;Nuovo Nome;PN;M999
+";
+
+fn workdir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("belfiore-import-{}-{}", name, std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("belfiore.txt"), CURRENT).unwrap();
+    fs::write(dir.join("istat.csv"), ISTAT).unwrap();
+    dir
+}
+
+fn import(dir: &PathBuf, args: &[&str]) -> (bool, String, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_belfiore-import"))
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .unwrap();
+    (
+        output.status.success(),
+        String::from_utf8(output.stdout).unwrap(),
+        String::from_utf8(output.stderr).unwrap(),
+    )
+}
+
+#[test]
+fn t_import_reports_changes() {
+    let dir = workdir("report");
+    let (ok, report, _) = import(
+        &dir,
+        &[
+            "--source",
+            "ISTAT",
+            "--extracted",
+            "2024-01-31",
+            "istat.csv",
+        ],
+    );
+    assert!(ok);
+    assert!(report.contains("Added: 1\n  + D571,BZ,FIE' ALLO SCILIAR,1,021031"));
+    assert!(report.contains("Renamed: 1\n  ~ M999 VECCHIO NOME -> NUOVO NOME"));
+    assert!(report
+        .contains("Existing entries not listed (kept as suppressed): 1\n  - G886,PN,PORCIA,1"));
+    assert!(report.contains("Suppressed entries not listed (kept): 2"));
+
+    // Sorted by name, old names and missing entries kept as suppressed
+    assert_eq!(
+        fs::read_to_string(dir.join("belfiore.txt")).unwrap(),
+        "A001,PD,ABANO BAGNI,0
+Z100,EE,ALBANIA,0
+D571,BZ,FIE' ALLO SCILIAR,1,021031
+E889,PN,MANIAGO,1,093025
+M999,PN,NUOVO NOME,1,099999
+G886,PN,PORCIA,0
+M999,PN,VECCHIO NOME,0"
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join("belfiore.meta")).unwrap(),
+        "source=ISTAT\nextracted=2024-01-31\n"
+    );
+}
+
+#[test]
+fn t_import_dry_run() {
+    let dir = workdir("dry-run");
+    let (ok, _, _) = import(&dir, &["--dry-run", "istat.csv"]);
+    assert!(ok);
+    assert_eq!(
+        fs::read_to_string(dir.join("belfiore.txt")).unwrap(),
+        CURRENT
+    );
+    assert!(!dir.join("belfiore.meta").exists());
+}
+
+#[test]
+fn t_import_validation() {
+    // Without the current data nothing is left for foreign countries
+    let dir = workdir("validation");
+    let (ok, _, errors) = import(&dir, &["--drop-missing", "istat.csv"]);
+    assert!(!ok);
+    assert!(errors.contains("no foreign country"));
+    assert_eq!(
+        fs::read_to_string(dir.join("belfiore.txt")).unwrap(),
+        CURRENT
+    );
+
+    let (ok, _, errors) = import(&dir, &["--extracted", "31/01/2024", "istat.csv"]);
+    assert!(!ok);
+    assert!(errors.contains("invalid extraction date"));
+    let (ok, _, errors) = import(&dir, &["--extracted", "2024-13-45", "istat.csv"]);
+    assert!(!ok);
+    assert!(errors.contains("invalid extraction date"));
+}
@@ -0,0 +1,99 @@
+#![cfg(test)]
+#![cfg(feature = "embedded-data")]
+extern crate codice_fiscale;
+use codice_fiscale::lineage::LineageKind;
+use codice_fiscale::*;
+
+#[test]
+fn t_merged_municipality_resolves_to_successor() {
+    let current = BELFIORE_STORE.current_successors("A726");
+    assert_eq!(current.len(), 1);
+    assert_eq!(current[0].name, "VALSAMOGGIA");
+    assert!(current[0].active);
+}
+
+#[test]
+fn t_active_municipality_resolves_to_itself() {
+    let current = BELFIORE_STORE.current_successors("E889");
+    assert_eq!(current.len(), 1);
+    assert_eq!(current[0].name, "MANIAGO");
+}
+
+#[test]
+fn t_renamed_municipality_keeps_its_code() {
+    let current = BELFIORE_STORE.current_successors("A001");
+    assert_eq!(current[0].name, "ABANO TERME");
+}
+
+#[test]
+fn t_unknown_code_has_no_successor() {
+    assert!(BELFIORE_STORE.current_successors("A000").is_empty());
+}
+
+#[test]
+fn t_predecessors() {
+    let mut names: Vec<&str> = BELFIORE_STORE
+        .predecessors("M320")
+        .iter()
+        .map(|m| &*m.name)
+        .collect();
+    names.sort_unstable();
+    assert_eq!(
+        names,
+        vec![
+            "BAZZANO",
+            "CASTELLO DI SERRAVALLE",
+            "CRESPELLANO",
+            "MONTEVEGLIO",
+            "SAVIGNO"
+        ]
+    );
+    assert!(BELFIORE_STORE.predecessors("E889").is_empty());
+
+    // B385 is also the code of Calatafimi-Segesta, which still exists
+    let madruzzo = BELFIORE_STORE.predecessors("M357");
+    assert!(madruzzo.iter().any(|m| m.name == "CALAVINO"));
+    assert!(madruzzo.iter().all(|m| m.province == "TN"));
+}
+
+#[test]
+fn t_lineage_is_consistent() {
+    for l in BELFIORE_STORE.lineage() {
+        assert!(
+            BELFIORE_STORE
+                .iter()
+                .any(|m| m.belfiore_code == l.predecessor && !m.active),
+            "{} still exists",
+            l.predecessor
+        );
+        assert!(BELFIORE_STORE.lookup_belfiore(&l.successor).is_some());
+        // A successor which kept its code under an older name incorporated the predecessor
+        if l.kind == LineageKind::Merger {
+            assert!(
+                BELFIORE_STORE
+                    .iter()
+                    .filter(|m| m.belfiore_code == l.successor)
+                    .all(|m| m.active),
+                "{} kept its code, so {} was incorporated",
+                l.successor,
+                l.predecessor
+            );
+        }
+    }
+    assert!(BELFIORE_STORE
+        .lineage()
+        .any(|l| l.kind == LineageKind::Incorporation));
+}
+
+#[test]
+fn t_codice_fiscale_present_day_place_of_birth() {
+    let cf = CodiceFiscale::parse("BLTMHL77S04D158H").unwrap();
+    assert_eq!(cf.get_person_data().place_of_birth.name, "CRESPELLANO");
+    let current = cf.get_present_day_places_of_birth();
+    assert_eq!(current[0].name, "VALSAMOGGIA");
+    let current = cf.get_present_day_places_of_birth_with_store(&*BELFIORE_STORE);
+    assert_eq!(current[0].name, "VALSAMOGGIA");
+
+    let abroad = CodiceFiscale::parse("BLTMHL77S04Z118Z").unwrap();
+    assert!(abroad.get_present_day_places_of_birth().is_empty());
+}
@@ -0,0 +1,237 @@
+#![cfg(test)]
+#![cfg(feature = "embedded-data")]
+extern crate codice_fiscale;
+use codice_fiscale::belfiore::{AltName, Belfiore, Language, LoadError};
+use codice_fiscale::*;
+use std::borrow::Cow;
+
+const ISTAT_SAMPLE: &str = "Codice Regione;Codice Comune formato alfanumerico;Denominazione (Italiana e straniera);Denominazione in italiano;Sigla automobilistica;Codice Catastale del comune\r
+06;093025;Maniago;Maniago;PN;E889\r
+21;021008;Bolzano/Bozen;Bolzano;BZ;A952\r
+";
+
+const ANPR_SAMPLE: &str =
+    "ID,DATAISTITUZIONE,DATACESSAZIONE,CODISTAT,CODCATASTALE,DENOMINAZIONE_IT,SIGLAPROVINCIA,STATO
+1,1861-03-17,9999-12-31,093025,E889,MANIAGO,PN,A
+2,1861-03-17,1928-05-30,,A001,\"ABANO BAGNI\",PD,C
+";
+
+#[test]
+fn t_load_native() {
+    let store =
+        Belfiore::from_reader("E889,PN,MANIAGO,1\nA001,PD,ABANO BAGNI,0\n".as_bytes()).unwrap();
+    assert_eq!(store.iter().count(), 2);
+    assert!(store.get_info("Maniago").unwrap().active);
+    assert!(!store.lookup_belfiore("A001").unwrap().active);
+}
+
+#[test]
+fn t_load_istat() {
+    let store = Belfiore::from_reader(ISTAT_SAMPLE.as_bytes()).unwrap();
+    let bolzano = store.lookup_belfiore("A952").unwrap();
+    assert_eq!(bolzano.name, "BOLZANO");
+    assert_eq!(bolzano.province, "BZ");
+    assert!(bolzano.active);
+    assert_eq!(bolzano.name_in(Language::German), "BOZEN");
+    assert!(store.lookup_belfiore("E889").unwrap().alt_names.is_empty());
+}
+
+#[test]
+fn t_load_alt_name_column() {
+    let data = "Codice Catastale del comune;Denominazione in italiano;Denominazione altra lingua;Sigla automobilistica
+L424;Trieste;Trst;TS
+";
+    let store = Belfiore::from_reader(data.as_bytes()).unwrap();
+    assert_eq!(store.get_info("Trst").unwrap().name, "TRIESTE");
+    assert_eq!(
+        store.lookup_belfiore("L424").unwrap().alt_names,
+        vec![AltName {
+            language: Language::Slovenian,
+            name: "TRST".into()
+        }]
+    );
+}
+
+#[test]
+fn t_load_latin1() {
+    let mut bytes = ISTAT_SAMPLE.as_bytes().to_vec();
+    bytes.extend_from_slice(b"02;007018;Ch\xe2tillon;Ch\xe2tillon;AO;C294\r\n");
+    let store = Belfiore::from_reader(&bytes[..]).unwrap();
+    assert_eq!(store.lookup_belfiore("C294").unwrap().name, "CHÂTILLON");
+}
+
+#[test]
+fn t_load_anpr() {
+    let store = Belfiore::from_reader(ANPR_SAMPLE.as_bytes()).unwrap();
+    assert!(store.lookup_belfiore("E889").unwrap().active);
+    let abano = store.lookup_belfiore("A001").unwrap();
+    assert_eq!(abano.name, "ABANO BAGNI");
+    assert!(!abano.active);
+}
+
+#[test]
+fn t_load_agenzia_entrate() {
+    let data = "Codice catastale;Denominazione;Provincia;Data fine validita
+E889;MANIAGO;PN;31/12/9999
+A001;ABANO BAGNI;PD;30/05/1928
+Z112;GERMANIA;EE;
+";
+    let store = Belfiore::from_reader(data.as_bytes()).unwrap();
+    assert!(store.lookup_belfiore("E889").unwrap().active);
+    assert!(!store.lookup_belfiore("A001").unwrap().active);
+    assert!(store.lookup_belfiore("Z112").unwrap().is_foreign());
+}
+
+#[test]
+fn t_load_errors() {
+    assert_eq!(
+        Belfiore::from_reader("".as_bytes()).err(),
+        Some(LoadError::Empty)
+    );
+    assert_eq!(
+        Belfiore::from_reader("E889,PN,MANIAGO,1\nE89,PN,MANIAGO,1".as_bytes()).err(),
+        Some(LoadError::InvalidLine {
+            line: 2,
+            reason: "invalid Belfiore code".to_string()
+        })
+    );
+    assert!(matches!(
+        Belfiore::from_reader("E889,PN,MANIAGO".as_bytes()),
+        Err(LoadError::InvalidLine { line: 1, .. })
+    ));
+    assert!(matches!(
+        Belfiore::from_reader("Z110,PN,FRANCIA,0".as_bytes()),
+        Err(LoadError::InvalidLine { line: 1, .. })
+    ));
+    assert_eq!(
+        Belfiore::from_reader("Codice Catastale del comune;Altro\nE889;X".as_bytes()).err(),
+        Some(LoadError::UnknownFormat)
+    );
+    assert!(matches!(
+        Belfiore::from_path("/nonexistent/belfiore.txt"),
+        Err(LoadError::Io(_))
+    ));
+}
+
+#[test]
+fn t_load_error_messages() {
+    fn load(data: &str) -> Result<usize, Box<dyn std::error::Error>> {
+        Ok(Belfiore::from_reader(data.as_bytes())?.iter().count())
+    }
+    assert_eq!(load("E889,PN,MANIAGO,1").unwrap(), 1);
+    assert_eq!(
+        load("E889,PN,MANIAGO,1\nE89,PN,MANIAGO,1")
+            .unwrap_err()
+            .to_string(),
+        "Line 2: invalid Belfiore code"
+    );
+    assert_eq!(LoadError::Empty.to_string(), "No entries found");
+}
+
+#[test]
+fn t_new_with_store() {
+    let store = Belfiore::from_reader("M999,PN,NUOVO COMUNE,1".as_bytes()).unwrap();
+    let persondata = PersonData {
+        name: "Michele".to_string(),
+        surname: "Beltrame".to_string(),
+        birthdate: "1977-11-04".to_string(),
+        gender: Gender::M,
+        place_of_birth: store.get_info("Nuovo comune").unwrap().clone(),
+    };
+    let cf = CodiceFiscale::new_with_store(&persondata, &store).unwrap();
+    // The embedded database doesn't know the new municipality
+    assert_eq!(
+        CodiceFiscale::new(&persondata).err(),
+        Some(Error::InvalidBelfioreCode)
+    );
+    assert_eq!(
+        CodiceFiscale::check(cf.get_codice()).err(),
+        Some(Error::InvalidBelfioreCode)
+    );
+    let parsed = CodiceFiscale::parse_with_store(cf.get_codice(), &store).unwrap();
+    assert_eq!(parsed.get_person_data().place_of_birth.name, "NUOVO COMUNE");
+}
+
+#[test]
+fn t_istat_codes() {
+    let store = Belfiore::from_reader(ISTAT_SAMPLE.as_bytes()).unwrap();
+    assert_eq!(store.belfiore_to_istat("A952"), Some("021008"));
+    assert_eq!(store.istat_to_belfiore("021008"), Some("A952"));
+    assert_eq!(store.istat_to_belfiore("21008"), Some("A952"));
+    assert_eq!(store.lookup_istat("999999"), None);
+
+    // A parsed codice fiscale can be joined to ISTAT-keyed data
+    let cf = CodiceFiscale::parse_with_store("BLTMHL77S04E889G", &store).unwrap();
+    assert_eq!(
+        cf.get_person_data().place_of_birth.istat_code.as_deref(),
+        Some("093025")
+    );
+
+    let store = Belfiore::from_reader(ANPR_SAMPLE.as_bytes()).unwrap();
+    assert_eq!(store.belfiore_to_istat("E889"), Some("093025"));
+    assert_eq!(store.belfiore_to_istat("A001"), None);
+
+    assert!(matches!(
+        Belfiore::from_reader("E889,PN,MANIAGO,1,93025".as_bytes()),
+        Err(LoadError::InvalidLine { line: 1, .. })
+    ));
+}
+
+#[test]
+fn t_lookup_belfiore_prefers_active() {
+    assert_eq!(
+        BELFIORE_STORE.lookup_belfiore("A001").unwrap().name,
+        "ABANO TERME"
+    );
+    let cf = CodiceFiscale::parse("RSSMRA70A41A001D");
+    assert_eq!(
+        cf.map(|cf| cf.get_person_data().place_of_birth.name.clone()),
+        Ok("ABANO TERME".into())
+    );
+}
+
+#[test]
+fn t_embedded_matches_text() {
+    // belfiore.txt is embedded in binary form: it must decode to what the text loader reads
+    let embedded = Belfiore::init();
+    let text = Belfiore::from_path("belfiore.txt").unwrap();
+    assert!(embedded.iter().eq(text.iter()));
+    assert!(
+        embedded
+            .iter()
+            .all(|m| matches!(m.name, Cow::Borrowed(_))
+                && matches!(m.belfiore_code, Cow::Borrowed(_)))
+    );
+}
+
+#[test]
+fn t_dataset_version() {
+    let store = Belfiore::from_reader("E889,PN,MANIAGO,1".as_bytes()).unwrap();
+    assert_eq!(store.version().source, "reader");
+    assert_eq!(store.version().extracted, None);
+    assert_eq!(
+        store.version().sha256,
+        "8368d0cccb9ff7708a5c8c222cda404f7c501d111fe5b57c0074732f259cdc3b"
+    );
+
+    // The embedded data is hashed as belfiore.txt
+    let file = Belfiore::from_path("belfiore.txt").unwrap();
+    assert_eq!(file.version().source, "belfiore.txt");
+    assert_eq!(file.version().sha256, BELFIORE_STORE.version().sha256);
+    assert!(!BELFIORE_STORE.version().source.is_empty());
+}
+
+#[test]
+fn t_dataset_version_of_codice_fiscale() {
+    let store = Belfiore::from_reader("E889,PN,MANIAGO,1".as_bytes())
+        .unwrap()
+        .with_version("test", None);
+    let cf = CodiceFiscale::parse_with_store("BLTMHL77S04E889G", &store).unwrap();
+    assert_eq!(cf.get_dataset_version(), Some(store.version()));
+    assert_eq!(cf.get_dataset_version().unwrap().source, "test");
+    // Shared with the store, not copied
+    assert!(std::ptr::eq(
+        cf.get_dataset_version().unwrap(),
+        store.version()
+    ));
+}
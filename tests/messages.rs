@@ -0,0 +1,58 @@
+#![cfg(test)]
+#![cfg(feature = "embedded-data")]
+extern crate codice_fiscale;
+use codice_fiscale::messages::Locale;
+use codice_fiscale::*;
+
+#[test]
+fn t_messages() {
+    let e = CodiceFiscale::parse("BLTMHL77S04E889Y").unwrap_err();
+    assert_eq!(e.to_string(), "Invalid check character");
+    assert_eq!(e.message(Locale::English), e.to_string());
+    assert_eq!(
+        e.message(Locale::Italian),
+        "Carattere di controllo non valido"
+    );
+
+    let e = Error::AmbiguousBirthdate(vec!["1910-11-04".to_string(), "2010-11-04".to_string()]);
+    assert_eq!(
+        e.message(Locale::Italian),
+        "Data di nascita ambigua, potrebbe essere 1910-11-04 o 2010-11-04"
+    );
+    let e = Error::AmbiguousPlace(vec![
+        "CASTRO (BG)".to_string(),
+        "CASTRO (LE)".to_string(),
+        "CASTRO (RM)".to_string(),
+    ]);
+    assert_eq!(
+        e.to_string(),
+        "Ambiguous place of birth, specify the province: CASTRO (BG), CASTRO (LE) or CASTRO (RM)"
+    );
+    assert_eq!(
+        Error::MissingField("birthdate").message(Locale::Italian),
+        "Manca il campo data di nascita"
+    );
+}
+
+#[test]
+fn t_locale() {
+    assert_eq!(Locale::from_code("it"), Some(Locale::Italian));
+    assert_eq!(Locale::from_code("it-IT"), Some(Locale::Italian));
+    assert_eq!(Locale::from_code("EN_us"), Some(Locale::English));
+    assert_eq!(Locale::from_code("de"), None);
+}
+
+#[test]
+fn t_std_error() {
+    fn check(codice: &str) -> Result<String, Box<dyn std::error::Error>> {
+        Ok(CodiceFiscale::parse(codice)?
+            .get_person_data()
+            .birthdate
+            .clone())
+    }
+    assert_eq!(check("BLTMHL77S04E889G").unwrap(), "1977-11-04");
+    assert_eq!(
+        check("BLTMHL77S04E889").unwrap_err().to_string(),
+        "Invalid length: a codice fiscale has 16 characters"
+    );
+}
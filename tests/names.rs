@@ -0,0 +1,175 @@
+#![cfg(test)]
+#![cfg(feature = "embedded-data")]
+extern crate codice_fiscale;
+use codice_fiscale::names::*;
+use codice_fiscale::*;
+
+// Real compound surnames and given names, with the letters of the codice fiscale
+const SURNAMES: &[(&str, &str)] = &[
+    ("De Luca", "DLC"),
+    ("D'Angelo", "DNG"),
+    ("D’Angelo", "DNG"),
+    ("D' Angelo", "DNG"),
+    ("Lo Russo", "LRS"),
+    ("Della Valle", "DLL"),
+    ("Di Maio", "DMI"),
+    ("Dell'Utri", "DLL"),
+    ("Dalla Chiesa", "DLL"),
+    ("Degli Esposti", "DGL"),
+    ("Del Piero", "DLP"),
+    ("La Rosa", "LRS"),
+    ("Li Causi", "LCS"),
+    ("De André", "DND"),
+    ("D'Alessandro", "DLS"),
+    ("Dall'Oglio", "DLL"),
+    ("Bassi-Gentile", "BSS"),
+    ("Sforza Cesarini", "SFR"),
+    ("Van der Berg", "VND"),
+    ("Lo", "LOX"),
+    ("Li", "LIX"),
+    ("Fo", "FOX"),
+];
+
+const NAMES: &[(&str, &str)] = &[
+    ("Maria Grazia", "MGR"),
+    ("Gian Luca", "GLC"),
+    ("Gian-Luca", "GLC"),
+    ("Gianluca", "GLC"),
+    ("Anna Maria", "NMR"),
+    ("Maria Teresa", "MTR"),
+    ("Pier Paolo", "PPL"),
+    ("Giovanni Battista", "GNN"),
+    ("Giuseppe Maria", "GPP"),
+    ("Gian Maria", "GMR"),
+    ("Anna-Lisa", "NLS"),
+    ("Pierluigi", "PLG"),
+    ("Edoardo", "DRD"),
+    ("Mattia", "MTT"),
+    ("Luigi", "LGU"),
+    ("Ugo", "GUO"),
+    ("Lia", "LIA"),
+    ("Al", "LAX"),
+];
+
+fn codice(name: &str, surname: &str) -> String {
+    CodiceFiscale::new(&PersonData {
+        name: name.to_string(),
+        surname: surname.to_string(),
+        birthdate: "1970-01-01".to_string(),
+        gender: Gender::M,
+        place_of_birth: BELFIORE_STORE.get_info("Roma").unwrap().clone(),
+    })
+    .unwrap()
+    .get_codice()
+    .to_string()
+}
+
+#[test]
+fn t_golden_surnames() {
+    for (surname, expected) in SURNAMES {
+        assert_eq!(&codice("Mario", surname)[..3], *expected, "{}", surname);
+    }
+}
+
+#[test]
+fn t_golden_names() {
+    for (name, expected) in NAMES {
+        assert_eq!(&codice(name, "Rossi")[3..6], *expected, "{}", name);
+    }
+}
+
+#[test]
+fn t_words() {
+    let found: Vec<(String, bool)> = words("dell’Acqua De Luca-Rossi")
+        .into_iter()
+        .map(|w| (w.text, w.particle))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            ("DELL".to_string(), true),
+            ("ACQUA".to_string(), false),
+            ("DE".to_string(), true),
+            ("LUCA".to_string(), false),
+            ("ROSSI".to_string(), false),
+        ]
+    );
+    assert!(words(" - ").is_empty());
+}
+
+#[test]
+fn t_normalize() {
+    assert_eq!(normalize("dall’ oglio"), "DALL'OGLIO");
+    assert_eq!(normalize("Bassi - Gentile"), "BASSI-GENTILE");
+    assert_eq!(normalize(" maria\tgrazia "), "MARIA GRAZIA");
+    assert_eq!(normalize("De Andrè"), "DE ANDRE");
+    assert_eq!(letters("D'Angelo"), letters("Dangelo"));
+}
+
+#[test]
+fn t_new_compound() {
+    let cf = CodiceFiscale::new(&PersonData {
+        name: "Maria Grazia".to_string(),
+        surname: "De Luca".to_string(),
+        birthdate: "1970-01-01".to_string(),
+        gender: Gender::F,
+        place_of_birth: BELFIORE_STORE.get_info("Roma").unwrap().clone(),
+    })
+    .unwrap();
+    assert_eq!(cf.get_codice(), "DLCMGR70A41H501F");
+    assert!(cf.is_surname_valid("DE LUCA"));
+    assert!(cf.is_surname_valid("Deluca"));
+    assert!(cf.is_name_valid("Maria-Grazia"));
+    assert!(!cf.is_name_valid("Maria"));
+
+    let cf = CodiceFiscale::new(&PersonData {
+        name: "Gian Luca".to_string(),
+        surname: "D'Angelo".to_string(),
+        birthdate: "1982-08-15".to_string(),
+        gender: Gender::M,
+        place_of_birth: BELFIORE_STORE.get_info("Maniago").unwrap().clone(),
+    })
+    .unwrap();
+    assert_eq!(cf.get_codice(), "DNGGLC82M15E889W");
+}
+
+#[test]
+fn t_trace() {
+    let trace = trace_name_component("Isacco");
+    assert_eq!(trace.letters, "ISACCO");
+    assert_eq!(trace.consonants, "SCC");
+    assert_eq!(trace.dropped_consonant, None);
+    assert_eq!(trace.code, "SCC");
+
+    let trace = trace_surname_component("Fo");
+    assert_eq!(trace.used_consonants, "F");
+    assert_eq!(trace.appended_vowels, "O");
+    assert_eq!(trace.padding, 1);
+    assert_eq!(
+        trace.to_string(),
+        "Letters: FO\n\
+         Consonants: F\n\
+         Consonants taken: F\n\
+         Less than three consonants, vowels added: O\n\
+         Less than three letters, X added: 1\n\
+         Code: FOX"
+    );
+
+    // Surnames never skip the second consonant
+    let trace = trace_surname_component("Della Valle");
+    assert_eq!(trace.consonants, "DLLVLL");
+    assert_eq!(trace.dropped_consonant, None);
+    let trace = trace_name_component("Maria Grazia");
+    assert_eq!(trace.dropped_consonant, Some('R'));
+    assert_eq!(trace.used_consonants, "MGR");
+}
+
+#[test]
+fn t_trace_matches_codice() {
+    for (surname, expected) in SURNAMES {
+        assert_eq!(&trace_surname_component(surname).code, expected);
+    }
+    for (name, expected) in NAMES {
+        assert_eq!(&trace_name_component(name).code, expected);
+    }
+}
@@ -0,0 +1,81 @@
+#![cfg(test)]
+#![cfg(feature = "embedded-data")]
+extern crate codice_fiscale;
+use codice_fiscale::*;
+
+#[test]
+fn t_lookup_province() {
+    let ss = province::lookup_province("ss").unwrap();
+    assert_eq!(ss.name, "Sassari");
+    assert_eq!(ss.istat_code.as_deref(), Some("090"));
+    assert_eq!(ss.nuts2(), Some("ITG2"));
+    assert!(ss.active);
+    assert!(province::lookup_province("XX").is_none());
+}
+
+#[test]
+fn t_historical_provinces() {
+    // Abolished in the Sardinian reorganization
+    let ci = province::lookup_province("CI").unwrap();
+    assert!(!ci.active);
+    assert_eq!(ci.successors, vec!["SU".to_string()]);
+    assert_eq!(ci.region.unwrap().name, "Sardegna");
+
+    // Re-coded
+    let fo = province::lookup_province("FO").unwrap();
+    assert_eq!(fo.successors, vec!["FC".to_string()]);
+
+    // Ceded after World War II
+    let pl = province::lookup_province("PL").unwrap();
+    assert!(pl.region.is_none());
+
+    // Every successor exists
+    assert!(province::provinces()
+        .flat_map(|p| p.successors.iter())
+        .all(|s| province::lookup_province(s).map_or(false, |p| p.active)));
+}
+
+#[test]
+fn t_regions() {
+    assert_eq!(province::regions().count(), 20);
+    let taa = province::lookup_region("04").unwrap();
+    assert_eq!(taa.nuts2, None);
+    let bz = province::lookup_province("BZ").unwrap();
+    assert_eq!(bz.nuts2(), Some("ITH1"));
+
+    let fvg = province::lookup_region("friuli-venezia giulia").unwrap();
+    let codes: Vec<&str> = province::provinces_in(fvg)
+        .map(|p| p.code.as_str())
+        .collect();
+    assert_eq!(codes, vec!["UD", "GO", "TS", "PN"]);
+    // Active provinces share the NUTS-2 code of their region
+    assert!(province::provinces()
+        .filter(|p| p.active && p.region.map_or(false, |r| r.nuts2.is_some()))
+        .all(|p| p.nuts2() == p.region.unwrap().nuts2));
+}
+
+#[test]
+fn t_every_municipality_has_a_province() {
+    assert!(BELFIORE_STORE
+        .italian()
+        .all(|m| m.province_info().is_some()));
+    assert!(BELFIORE_STORE.foreign().all(|m| m.region().is_none()));
+}
+
+#[test]
+fn t_municipality_region() {
+    let maniago = BELFIORE_STORE.get_info("Maniago").unwrap();
+    assert_eq!(maniago.region().unwrap().istat_code, "06");
+    let sardegna = province::lookup_region("Sardegna").unwrap();
+    assert!(BELFIORE_STORE
+        .by_region(sardegna)
+        .all(|m| ["SS", "NU", "CA", "OR", "SU"].contains(&&*m.province)));
+}
+
+#[test]
+fn t_parse_birth_region() {
+    let cf = CodiceFiscale::parse("RSSMRA70A41H501W").unwrap();
+    assert_eq!(cf.get_birth_region().unwrap().name, "Lazio");
+    let cf = CodiceFiscale::parse("BLTMHL77S04Z118Z").unwrap();
+    assert!(cf.get_birth_region().is_none());
+}
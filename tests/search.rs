@@ -0,0 +1,127 @@
+#![cfg(test)]
+#![cfg(feature = "embedded-data")]
+extern crate codice_fiscale;
+use codice_fiscale::search::SearchFilter;
+use codice_fiscale::*;
+
+#[test]
+fn t_search_prefix() {
+    let found = BELFIORE_STORE.search_prefix("Maniàg", &SearchFilter::default(), 10);
+    assert_eq!(found[0].belfiore_code, "E889");
+    assert!(found.iter().all(|m| m.name.starts_with("MANIA")));
+}
+
+#[test]
+fn t_search_prefix_ranking() {
+    // The existing municipality comes before the suppressed one with the same name
+    let found = BELFIORE_STORE.search_prefix("abano", &SearchFilter::default(), 10);
+    assert_eq!(found[0].name, "ABANO TERME");
+    assert!(found[0].active);
+    assert_eq!(found[1].name, "ABANO BAGNI");
+}
+
+#[test]
+fn t_search_prefix_apostrophe() {
+    let found = BELFIORE_STORE.search_prefix("sant ang", &SearchFilter::default(), 1000);
+    assert!(found.iter().any(|m| m.name.starts_with("SANT'ANGELO")));
+    let found = BELFIORE_STORE.search_prefix("Città di Cas", &SearchFilter::default(), 10);
+    assert_eq!(found[0].name, "CITTA' DI CASTELLO");
+}
+
+#[test]
+fn t_search_filter() {
+    let filter = SearchFilter {
+        province: Some("co".to_string()),
+        active: None,
+    };
+    let found = BELFIORE_STORE.search_prefix("abbadia", &filter, 10);
+    assert_eq!(found.len(), 2);
+    assert!(found.iter().all(|m| m.belfiore_code == "A002"));
+
+    let filter = SearchFilter {
+        province: None,
+        active: Some(true),
+    };
+    let found = BELFIORE_STORE.search_prefix("abano", &filter, 10);
+    assert_eq!(found.len(), 1);
+}
+
+#[test]
+fn t_search_fuzzy() {
+    let found = BELFIORE_STORE.search_fuzzy("Rovgo", 1, &SearchFilter::default(), 10);
+    assert!(found.iter().any(|m| m.municipality.name == "ROVIGO"));
+    assert!(found.iter().all(|m| m.distance == 1));
+
+    let found = BELFIORE_STORE.search_fuzzy("Rovgio", 2, &SearchFilter::default(), 100);
+    assert!(found.windows(2).all(|w| w[0].distance <= w[1].distance));
+}
+
+#[test]
+fn t_search_fuzzy_prefix() {
+    // While typing, a partial name with a typo still finds the municipality
+    let filter = SearchFilter {
+        province: Some("PN".to_string()),
+        active: Some(true),
+    };
+    let found = BELFIORE_STORE.search_fuzzy("manag", 1, &filter, 10);
+    assert_eq!(found[0].municipality.name, "MANIAGO");
+    assert_eq!(found[0].distance, 1);
+}
+
+#[test]
+fn t_search_fuzzy_matches_exhaustive() {
+    // Pruning must not lose any match compared to checking every name
+    let store = belfiore::Belfiore::init();
+    let found = store.search_fuzzy("mon", 1, &SearchFilter::default(), usize::MAX);
+    let expected = store.search_prefix("", &SearchFilter::default(), usize::MAX);
+    let expected = expected
+        .iter()
+        .filter(|m| {
+            m.names().any(|name| {
+                let name: Vec<char> = name.chars().map(fold).collect();
+                (0..=name.len()).any(|len| levenshtein(&name[..len], &['M', 'O', 'N']) <= 1)
+            })
+        })
+        .count();
+    assert_eq!(found.len(), expected);
+}
+
+fn fold(c: char) -> char {
+    match c {
+        'Ä' => 'A',
+        'Ë' => 'E',
+        'Ö' => 'O',
+        'Ü' => 'U',
+        c => c,
+    }
+}
+
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut next = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            next.push((row[j] + cost).min(row[j + 1] + 1).min(next[j] + 1));
+        }
+        row = next;
+    }
+    row[b.len()]
+}
+
+#[test]
+fn t_search_alt_names() {
+    let found = BELFIORE_STORE.search_prefix("boze", &SearchFilter::default(), 10);
+    assert_eq!(found[0].name, "BOLZANO");
+
+    // Diacritics and abbreviations are folded: ST. ULRICH IN GRÖDEN
+    let found = BELFIORE_STORE.search_prefix("st ulrich in groden", &SearchFilter::default(), 10);
+    assert_eq!(found[0].name, "ORTISEI");
+
+    let found = BELFIORE_STORE.search_fuzzy("sterzimg", 1, &SearchFilter::default(), 10);
+    assert_eq!(found[0].municipality.name, "VIPITENO");
+
+    // Matching with both names, a municipality is returned once
+    let found = BELFIORE_STORE.search_prefix("lana", &SearchFilter::default(), 10);
+    assert_eq!(found.iter().filter(|m| m.name == "LANA").count(), 1);
+}
@@ -0,0 +1,114 @@
+#![cfg(test)]
+extern crate codice_fiscale;
+use codice_fiscale::belfiore::{Municipality, MunicipalityStore};
+use codice_fiscale::*;
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::collections::HashMap;
+
+/// A store backed by a map, counting lookups like a cache would
+struct MapStore {
+    by_code: HashMap<String, Municipality>,
+    lookups: Cell<usize>,
+}
+
+impl MapStore {
+    fn new() -> Self {
+        let mut by_code = HashMap::new();
+        by_code.insert(
+            "H501".to_string(),
+            Municipality {
+                name: "ROMA".into(),
+                province: "RM".into(),
+                belfiore_code: "H501".into(),
+                active: true,
+                ..Default::default()
+            },
+        );
+        MapStore {
+            by_code,
+            lookups: Cell::new(0),
+        }
+    }
+}
+
+impl MunicipalityStore for MapStore {
+    fn get_info(&self, municipality_name: &str) -> Option<Cow<'_, Municipality>> {
+        self.lookups.set(self.lookups.get() + 1);
+        self.by_code
+            .values()
+            .find(|m| m.name.eq_ignore_ascii_case(municipality_name))
+            .map(Cow::Borrowed)
+    }
+
+    fn lookup_belfiore(&self, belfiore: &str) -> Option<Cow<'_, Municipality>> {
+        self.lookups.set(self.lookups.get() + 1);
+        self.by_code.get(belfiore).map(Cow::Borrowed)
+    }
+}
+
+#[test]
+fn t_custom_store() {
+    let store = MapStore::new();
+    let cf = CodiceFiscale::parse_with_store("RSSMRA70A41H501W", &store).unwrap();
+    assert_eq!(cf.get_person_data().place_of_birth.name, "ROMA");
+    assert_eq!(store.lookups.get(), 1);
+    // The provinces are built in even without the embedded municipalities
+    assert_eq!(cf.get_birth_region().unwrap().name, "Lazio");
+    assert_eq!(
+        CodiceFiscale::check_with_store("BLTMHL77S04E889G", &store).err(),
+        Some(Error::InvalidBelfioreCode)
+    );
+
+    let persondata = PersonData {
+        name: "Maria".to_string(),
+        surname: "Rossi".to_string(),
+        birthdate: "1970-01-01".to_string(),
+        gender: Gender::F,
+        place_of_birth: store.get_info("Roma").unwrap().into_owned(),
+    };
+    let cf = CodiceFiscale::new_with_store(&persondata, &store).unwrap();
+    assert_eq!(cf.get_codice(), "RSSMRA70A41H501W");
+}
+
+#[test]
+#[cfg(feature = "embedded-data")]
+fn t_dyn_store() {
+    let stores: Vec<Box<dyn MunicipalityStore>> = vec![
+        Box::new(MapStore::new()),
+        Box::new(belfiore::Belfiore::init()),
+    ];
+    for store in &stores {
+        assert!(CodiceFiscale::check_with_store("RSSMRA70A41H501W", store.as_ref()).is_ok());
+    }
+    assert!(CodiceFiscale::check_with_store("BLTMHL77S04E889G", &*BELFIORE_STORE).is_ok());
+}
+
+#[test]
+fn t_custom_store_without_lineage() {
+    let store = MapStore::new();
+    let cf = CodiceFiscale::parse_with_store("RSSMRA70A41H501W", &store).unwrap();
+    let current = cf.get_present_day_places_of_birth_with_store(&store);
+    assert_eq!(current.len(), 1);
+    assert_eq!(current[0].name, "ROMA");
+    assert!(store.current_successors("A726").is_empty());
+}
+
+#[test]
+fn t_custom_store_without_countries() {
+    let store = MapStore::new();
+    let cf = CodiceFiscale::parse_with_store("RSSMRA70A41H501W", &store).unwrap();
+    assert!(cf.get_birth_country_with_store(&store).is_none());
+    assert!(store.birth_country("Z118").is_none());
+}
+
+#[test]
+fn t_custom_store_without_istat() {
+    assert!(MapStore::new().lookup_istat("058091").is_none());
+}
+
+#[test]
+fn t_store_without_version() {
+    let cf = CodiceFiscale::parse_with_store("RSSMRA70A41H501W", &MapStore::new()).unwrap();
+    assert_eq!(cf.get_dataset_version(), None);
+}
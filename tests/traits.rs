@@ -0,0 +1,102 @@
+#![cfg(test)]
+#![cfg(feature = "embedded-data")]
+extern crate codice_fiscale;
+use codice_fiscale::belfiore::Municipality;
+use codice_fiscale::*;
+use std::collections::{BTreeSet, HashMap};
+use std::convert::TryFrom;
+
+#[test]
+fn t_codice_fiscale_identity() {
+    let computed = CodiceFiscale::new(&PersonData {
+        name: "Michele".to_string(),
+        surname: "Beltrame".to_string(),
+        birthdate: "1977-11-04".to_string(),
+        gender: Gender::M,
+        place_of_birth: BELFIORE_STORE.get_info("Maniago").unwrap().clone(),
+    })
+    .unwrap();
+    let parsed: CodiceFiscale = "BLTMHL77S04E889G".parse().unwrap();
+    // Parsing loses the full names, but it's the same codice
+    assert_ne!(computed.get_person_data(), parsed.get_person_data());
+    assert_eq!(computed, parsed);
+
+    let mut counts = HashMap::new();
+    *counts.entry(computed).or_insert(0) += 1;
+    *counts.entry(parsed).or_insert(0) += 1;
+    assert_eq!(counts.len(), 1);
+}
+
+#[test]
+fn t_codice_fiscale_conversions() {
+    let cf = CodiceFiscale::try_from("RSSMRA70A41H501W").unwrap();
+    assert_eq!(format!("{}", cf), "RSSMRA70A41H501W");
+    assert_eq!(
+        CodiceFiscale::try_from("RSSMRA70A41H501W".to_string()).unwrap(),
+        cf
+    );
+    assert_eq!(
+        "BLTMHL77S04E889Y".parse::<CodiceFiscale>().err(),
+        Some(Error::InvalidCheckChar)
+    );
+    // Untrusted input is rejected before the check char is computed
+    assert_eq!(
+        "BLTMHL77S04E88-G".parse::<CodiceFiscale>().err(),
+        Some(Error::InvalidCheckChar)
+    );
+    assert_eq!(
+        CodiceFiscale::try_from("BLTMHL77S04E8ÈG").err(),
+        Some(Error::InvalidLength)
+    );
+    assert_eq!(
+        CodiceFiscale::try_from("ÈLTMHL77S04E889").err(),
+        Some(Error::InvalidLength)
+    );
+
+    let sorted: Vec<String> = ["RSSMRA70A41H501W", "BLTMHL77S04E889G", "MNZNCL90E12E889P"]
+        .iter()
+        .map(|c| c.parse::<CodiceFiscale>().unwrap())
+        .collect::<BTreeSet<_>>()
+        .iter()
+        .map(|cf| cf.to_string())
+        .collect();
+    assert_eq!(
+        sorted,
+        vec!["BLTMHL77S04E889G", "MNZNCL90E12E889P", "RSSMRA70A41H501W"]
+    );
+}
+
+#[test]
+fn t_gender() {
+    assert_eq!(Gender::try_from('M'), Ok(Gender::M));
+    assert_eq!(Gender::try_from('f'), Ok(Gender::F));
+    assert_eq!(Gender::try_from('X'), Err(Error::InvalidGender));
+    assert_eq!(" F ".parse::<Gender>(), Ok(Gender::F));
+    assert_eq!("MF".parse::<Gender>(), Err(Error::InvalidGender));
+    assert_eq!("".parse::<Gender>(), Err(Error::InvalidGender));
+    assert_eq!(Gender::M.to_string(), "M");
+    assert!(Gender::M < Gender::F);
+}
+
+#[test]
+fn t_municipality() {
+    let maniago = Municipality::try_from("e889").unwrap();
+    assert_eq!(maniago.to_string(), "MANIAGO (PN)");
+    assert_eq!("maniago".parse::<Municipality>().unwrap(), maniago);
+    assert_eq!(
+        "Q000".parse::<Municipality>().err(),
+        Some(Error::InvalidBelfioreCode)
+    );
+    assert_eq!(
+        "Atlantide".parse::<Municipality>().err(),
+        Some(Error::UnknownPlace("Atlantide".to_string()))
+    );
+
+    // Same order as belfiore.txt
+    let mut all: Vec<&Municipality> = BELFIORE_STORE.iter().collect();
+    let unsorted = all.clone();
+    all.sort();
+    assert!(all == unsorted);
+    let unique: BTreeSet<&Municipality> = BELFIORE_STORE.iter().collect();
+    assert_eq!(unique.len(), all.len());
+}
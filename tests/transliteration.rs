@@ -0,0 +1,112 @@
+#![cfg(test)]
+#![cfg(feature = "embedded-data")]
+extern crate codice_fiscale;
+use codice_fiscale::transliteration::*;
+use codice_fiscale::*;
+
+fn person(name: &str, surname: &str, birthdate: &str, gender: Gender, code: &str) -> PersonData {
+    PersonData {
+        name: name.to_string(),
+        surname: surname.to_string(),
+        birthdate: birthdate.to_string(),
+        gender,
+        place_of_birth: BELFIORE_STORE.lookup_belfiore(code).unwrap().clone(),
+    }
+}
+
+#[test]
+fn t_transliterate() {
+    assert_eq!(transliterate("Иванов"), "IVANOV");
+    assert_eq!(transliterate("Щукина Юлия"), "SHCHUKINA IULIIA");
+    assert_eq!(transliterate("Љубиша Ђорђевић"), "LJUBISHA DORDEVIC");
+    assert_eq!(
+        transliterate("Γιώργος Παπαδόπουλος"),
+        "GIORGOS PAPADOPOULOS"
+    );
+    assert_eq!(transliterate("Ευάγγελος"), "EVANGELOS");
+    assert_eq!(transliterate("Ευθύμιος"), "EFTHYMIOS");
+    assert_eq!(transliterate("Strauß Łukasz"), "STRAUSS ŁUKASZ");
+}
+
+#[test]
+fn t_new_cyrillic_and_greek() {
+    let cf =
+        CodiceFiscale::new(&person("Иван", "Иванов", "1980-05-04", Gender::M, "Z154")).unwrap();
+    assert_eq!(cf.get_codice(), "VNVVNI80E04Z154C");
+    assert!(cf.is_surname_valid("IVANOV"));
+
+    let cf = CodiceFiscale::new(&person(
+        "Γεωργία",
+        "Παπαδοπούλου",
+        "1985-03-02",
+        Gender::F,
+        "Z115",
+    ))
+    .unwrap();
+    assert_eq!(cf.get_codice(), "PPDGRG85C42Z115S");
+}
+
+#[test]
+fn t_new_unsupported_script() {
+    assert_eq!(
+        CodiceFiscale::new(&person("محمد", "Rossi", "1980-05-04", Gender::M, "Z330")).err(),
+        Some(Error::InvalidName)
+    );
+    assert_eq!(
+        CodiceFiscale::new(&person("Wei", "王", "1980-05-04", Gender::M, "Z210")).err(),
+        Some(Error::InvalidSurname)
+    );
+}
+
+#[test]
+fn t_mrz_spelling() {
+    let russian = person("Иван", "Иванов", "1980-05-04", Gender::M, "Z154");
+    let latin = Transliteration::Mrz("IVANOFF<<IWAN".to_string())
+        .apply(&russian)
+        .unwrap();
+    assert_eq!(
+        CodiceFiscale::new(&latin).unwrap().get_codice(),
+        "VNFWNI80E04Z154G"
+    );
+
+    let line = "P<RUSSHEVCHENKO<<ALEXEI<<<<<<<<<<<<<<<<<<<<<";
+    assert_eq!(line.len(), 44);
+    let latin = Transliteration::Mrz(line.to_string())
+        .apply(&russian)
+        .unwrap();
+    assert_eq!(latin.surname, "SHEVCHENKO");
+    assert_eq!(latin.name, "ALEXEI");
+
+    let chinese = person("伟", "王", "1980-05-04", Gender::M, "Z210");
+    let latin = Transliteration::Mrz("WANG<<WEI".to_string())
+        .apply(&chinese)
+        .unwrap();
+    assert!(CodiceFiscale::new(&latin).is_ok());
+    assert_eq!(
+        Transliteration::Builtin.apply(&chinese).err(),
+        Some(Error::InvalidSurname)
+    );
+    assert_eq!(
+        Transliteration::Mrz("WANG".to_string())
+            .apply(&chinese)
+            .err(),
+        Some(Error::InvalidName)
+    );
+}
+
+#[test]
+fn t_mrz_non_ascii() {
+    let russian = person("Иван", "Иванов", "1980-05-04", Gender::M, "Z154");
+    let line = "P<RUÉSHEVCHENKO<<ALEXEI<<<<<<<<<<<<<<<<<<<<";
+    assert_eq!(line.len(), 44);
+    assert_eq!(
+        Transliteration::Mrz(line.to_string()).apply(&russian).err(),
+        Some(Error::InvalidSurname)
+    );
+    assert_eq!(
+        Transliteration::Mrz("ИВАНОВ<<ИВАН".to_string())
+            .apply(&russian)
+            .err(),
+        Some(Error::InvalidSurname)
+    );
+}